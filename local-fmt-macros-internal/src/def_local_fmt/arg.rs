@@ -3,6 +3,8 @@ use std::path::PathBuf;
 use syn::parse::ParseStream;
 use syn::{Ident, LitStr};
 
+use crate::utils::{case::RenameRule, whitespace::Whitespace};
+
 #[derive(Debug)]
 pub struct MessageField {
     pub ty: Ident,
@@ -13,9 +15,6 @@ impl syn::parse::Parse for MessageField {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let ty: Ident = input.parse()?;
         if !input.peek(syn::token::Brace) {
-            if !input.is_empty() {
-                let _: syn::Token![,] = input.parse()?;
-            }
             return Ok(Self { ty, fields: None });
         };
         let content;
@@ -47,19 +46,52 @@ impl syn::parse::Parse for MessageField {
 
 pub struct Args {
     pub name: Ident,
+    /// `name = static NAME`, defining a lazily initialized `static` instead of a `const`,
+    /// so that the messages can be loaded into allocated fields such as `String`.
+    pub is_static: bool,
     pub lang: Ident,
     pub message: MessageField,
     pub supplier: syn::Expr,
-    pub file_type: ArgFileType,
-    pub path: ArgPath,
+    /// The sources to load, later sources override earlier ones key by key.
+    pub sources: Vec<ArgSource>,
+    pub layer_report: Option<Ident>,
+    pub lang_names: ArgLangNames,
+    pub key_names: ArgKeyNames,
+    pub whitespace: ArgWhitespace,
+    /// `accessors = EN`, generating documented accessor methods from the messages of a source language.
+    pub accessors: Option<Ident>,
+    pub unused_args: ArgUnusedArgs,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ArgFileType {
     Toml,
     Json,
     Yaml,
 }
 
+impl ArgFileType {
+    /// The extensions from which the file type is inferred.
+    pub const EXTENSIONS: &'static [&'static str] = &["toml", "json", "jsonc", "yaml", "yml"];
+
+    pub fn from_extension(extension: &std::ffi::OsStr) -> Option<Self> {
+        match extension.to_str()? {
+            "toml" => Some(Self::Toml),
+            "json" | "jsonc" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Toml => "toml",
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+        }
+    }
+}
+
 impl syn::parse::Parse for ArgFileType {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let lit: LitStr = input.parse()?;
@@ -67,7 +99,7 @@ impl syn::parse::Parse for ArgFileType {
             "toml" => Ok(Self::Toml),
             "json" => Ok(Self::Json),
             "yaml" => Ok(Self::Yaml),
-            _ => Err(syn::Error::new(lit.span(), "expected toml or json")),
+            _ => Err(syn::Error::new(lit.span(), "expected toml, json or yaml")),
         }
     }
 }
@@ -78,18 +110,294 @@ pub enum ArgPath {
     Folder(PathBuf),
 }
 
-impl syn::parse::Parse for Args {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        mod kw {
-            syn::custom_keyword!(name);
-            syn::custom_keyword!(lang);
-            syn::custom_keyword!(message);
-            syn::custom_keyword!(supplier);
-            syn::custom_keyword!(file_type);
-            syn::custom_keyword!(lang_file);
-            syn::custom_keyword!(lang_folder);
+pub struct ArgSource {
+    /// The file type of every file of the source, or `None` to infer it from each extension.
+    pub file_type: Option<ArgFileType>,
+    pub path: ArgPath,
+    /// The path as written in the macro invocation.
+    pub name: String,
+}
+
+/// Parses a table such as `{ key = "value", ... }`.
+fn parse_table<K: syn::parse::Parse, V: syn::parse::Parse>(
+    input: ParseStream,
+) -> syn::Result<Vec<(K, V)>> {
+    let content;
+    syn::braced!(content in input);
+
+    let mut entries = Vec::new();
+    while !content.is_empty() {
+        let key: K = content.parse()?;
+        let _: syn::Token![=] = content.parse()?;
+        let value: V = content.parse()?;
+        entries.push((key, value));
+
+        if content.is_empty() {
+            break;
+        }
+        let _: syn::Token![,] = content.parse()?;
+    }
+    Ok(entries)
+}
+
+/// How the language names in the files map to the variants of the `lang` enum.
+#[derive(Default)]
+pub struct ArgLangNames {
+    /// `lang_names = { EnUs = "en-US" }`, mapping a variant to its name in the files.
+    pub names: Vec<(Ident, LitStr)>,
+    /// `lang_rename = "PascalCase"`, converting the other names with a rename rule.
+    pub rename: Option<RenameRule>,
+}
+
+impl ArgLangNames {
+    /// Returns the variant of a language name found in the files.
+    pub fn variant(&self, name: &str) -> String {
+        if let Some((variant, _)) = self.names.iter().find(|(_, lit)| lit.value() == name) {
+            return variant.to_string();
         }
+        match self.rename {
+            Some(rule) => rule.apply(name),
+            None => name.to_string(),
+        }
+    }
+}
+
+/// A path of fields such as `auth.sign_in`.
+pub struct ArgKeyPath(pub Vec<Ident>);
+
+impl syn::parse::Parse for ArgKeyPath {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut path = vec![input.parse::<Ident>()?];
+        while input.peek(syn::Token![.]) {
+            let _: syn::Token![.] = input.parse()?;
+            path.push(input.parse()?);
+        }
+        Ok(Self(path))
+    }
+}
+
+/// How the keys in the files map to the fields of the `message` struct.
+#[derive(Default)]
+pub struct ArgKeyNames {
+    /// `rename = { auth.two_factor = "2fa" }`, mapping a field to its key in the files.
+    pub names: Vec<(ArgKeyPath, LitStr)>,
+    /// `rename_all = "kebab-case"`, the naming convention of the other keys in the files.
+    pub rename_all: Option<RenameRule>,
+}
+
+impl ArgKeyNames {
+    /// Returns the field of a key found in the files, where `parents` are the fields it is nested in.
+    /// Returns an error message if the key does not follow the `rename_all` convention.
+    pub fn field(&self, parents: &[String], key: &str) -> Result<String, String> {
+        let renamed = self.names.iter().find_map(|(path, lit)| {
+            let (field, path) = path.0.split_last()?;
+            let matches = lit.value() == key
+                && path.len() == parents.len()
+                && path.iter().zip(parents).all(|(a, b)| a == b);
+            matches.then(|| field.to_string())
+        });
+        if let Some(field) = renamed {
+            return Ok(field);
+        }
+
+        let Some(rule) = self.rename_all else {
+            return Ok(key.to_string());
+        };
+        let field = RenameRule::SnakeCase.apply(key);
+        if rule.apply(&field) != key {
+            return Err(format!(
+                "the key does not follow the {} convention of rename_all",
+                rule.name()
+            ));
+        }
+        Ok(field)
+    }
+}
+
+/// The whitespace options of the messages.
+#[derive(Default)]
+pub struct ArgWhitespace {
+    /// `whitespace = [trim]`, the options of every message.
+    pub default: Whitespace,
+    /// `key_whitespace = { help = [dedent] }`, the options of the messages at or under a field,
+    /// replacing the default options.
+    pub keys: Vec<(ArgKeyPath, Whitespace)>,
+}
+
+impl ArgWhitespace {
+    /// Returns the options of the message at the path of fields,
+    /// taken from the longest matching path of `key_whitespace`.
+    pub fn for_key(&self, path: &[String]) -> Whitespace {
+        self.keys
+            .iter()
+            .filter(|(key, _)| {
+                key.0.len() <= path.len() && key.0.iter().zip(path).all(|(a, b)| a == b)
+            })
+            .max_by_key(|(key, _)| key.0.len())
+            .map_or(self.default, |(_, whitespace)| *whitespace)
+    }
+}
+
+/// The messages whose languages may leave some of their arguments unused.
+#[derive(Default)]
+pub struct ArgUnusedArgs {
+    /// `allow_unused_args = true`, allowing unused arguments in every message.
+    pub all: bool,
+    /// `allow_unused_args = [greeting, inbox]`, allowing them in the messages at or under a field.
+    pub keys: Vec<ArgKeyPath>,
+}
+
+impl ArgUnusedArgs {
+    /// Returns true if the message at the path of fields may leave arguments unused.
+    pub fn for_key(&self, path: &[String]) -> bool {
+        self.all
+            || self
+                .keys
+                .iter()
+                .any(|key| key.0.len() <= path.len() && key.0.iter().zip(path).all(|(a, b)| a == b))
+    }
+}
+
+impl syn::parse::Parse for ArgUnusedArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitBool) {
+            let lit: syn::LitBool = input.parse()?;
+            return Ok(Self {
+                all: lit.value,
+                keys: Vec::new(),
+            });
+        }
+        let content;
+        syn::bracketed!(content in input);
+        let keys =
+            content.parse_terminated(<ArgKeyPath as syn::parse::Parse>::parse, syn::Token![,])?;
+        Ok(Self {
+            all: false,
+            keys: keys.into_iter().collect(),
+        })
+    }
+}
+
+mod kw {
+    syn::custom_keyword!(name);
+    syn::custom_keyword!(allow_unused_args);
+    syn::custom_keyword!(lang);
+    syn::custom_keyword!(message);
+    syn::custom_keyword!(supplier);
+    syn::custom_keyword!(file_type);
+    syn::custom_keyword!(lang_file);
+    syn::custom_keyword!(lang_folder);
+    syn::custom_keyword!(lang_layers);
+    syn::custom_keyword!(layer_report);
+    syn::custom_keyword!(lang_names);
+    syn::custom_keyword!(lang_rename);
+    syn::custom_keyword!(rename);
+    syn::custom_keyword!(rename_all);
+    syn::custom_keyword!(whitespace);
+    syn::custom_keyword!(key_whitespace);
+    syn::custom_keyword!(accessors);
+}
+
+fn crate_root() -> PathBuf {
+    #[allow(clippy::panic)]
+    let crate_root = std::env::var("CARGO_MANIFEST_DIR")
+        .unwrap_or_else(|e| panic!("failed to get CARGO_MANIFEST_DIR: {}. This environment variable should be set by Cargo during build.", e));
+    PathBuf::from(crate_root)
+}
 
+/// Fails with an error if an argument was given twice.
+fn set_once<T>(
+    slot: &mut Option<T>,
+    value: T,
+    span: proc_macro2::Span,
+    name: &str,
+) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new(
+            span,
+            format!("duplicate argument `{}`", name),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// A `lang_file` or `lang_folder` argument, before the `file_type` of the invocation is known.
+struct PartialSource {
+    file_type: Option<ArgFileType>,
+    path: ArgPath,
+    lit: LitStr,
+}
+
+impl PartialSource {
+    /// Parses `lang_file = "..."` or `lang_folder = "..."`, returning `None` if neither follows.
+    fn parse_path(input: ParseStream) -> syn::Result<Option<(ArgPath, LitStr)>> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::lang_file) {
+            let _: kw::lang_file = input.parse()?;
+            let _: syn::Token![=] = input.parse()?;
+            let lit: LitStr = input.parse()?;
+            Ok(Some((ArgPath::File(crate_root().join(lit.value())), lit)))
+        } else if lookahead.peek(kw::lang_folder) {
+            let _: kw::lang_folder = input.parse()?;
+            let _: syn::Token![=] = input.parse()?;
+            let lit: LitStr = input.parse()?;
+            Ok(Some((ArgPath::Folder(crate_root().join(lit.value())), lit)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Falls back to the `file_type` of the invocation when the source has none.
+    fn resolve(self, file_type: Option<ArgFileType>) -> ArgSource {
+        ArgSource {
+            file_type: self.file_type.or(file_type),
+            path: self.path,
+            name: self.lit.value(),
+        }
+    }
+}
+
+/// A layer of `lang_layers`, such as `(file_type = "json", lang_file = "overrides.json")`.
+impl syn::parse::Parse for PartialSource {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        syn::parenthesized!(content in input);
+
+        let mut file_type = None;
+        let mut path = None;
+        while !content.is_empty() {
+            if content.peek(kw::file_type) {
+                let kw: kw::file_type = content.parse()?;
+                let _: syn::Token![=] = content.parse()?;
+                set_once(&mut file_type, content.parse()?, kw.span, "file_type")?;
+            } else {
+                let span = content.span();
+                let Some(parsed) = Self::parse_path(&content)? else {
+                    return Err(content.error("expected file_type, lang_file or lang_folder"));
+                };
+                set_once(&mut path, parsed, span, "lang_file or lang_folder")?;
+            }
+
+            if content.is_empty() {
+                break;
+            }
+            let _: syn::Token![,] = content.parse()?;
+        }
+
+        let Some((path, lit)) = path else {
+            return Err(content.error("expected lang_file or lang_folder"));
+        };
+        Ok(Self {
+            file_type,
+            path,
+            lit,
+        })
+    }
+}
+
+impl syn::parse::Parse for Args {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         macro_rules! parse {
             ($ident:ident) => {
                 parse!($ident, Ident);
@@ -105,48 +413,152 @@ impl syn::parse::Parse for Args {
             };
         }
 
-        parse!(name);
-        parse!(lang);
-        parse!(message, MessageField, without_comma);
+        let _: kw::name = input.parse()?;
+        let _: syn::Token![=] = input.parse()?;
+        let is_static = input.parse::<Option<syn::Token![static]>>()?.is_some();
+        let name: Ident = input.parse()?;
+        let _: syn::Token![,] = input.parse()?;
 
-        if message.fields.is_some() {
-            let _: syn::Token![,] = input.parse()?;
-        }
+        parse!(lang);
+        parse!(message, MessageField);
 
         parse!(supplier, syn::Expr);
 
-        parse!(file_type, ArgFileType);
-
-        let crate_root = {
-            #[allow(clippy::panic)]
-            let crate_root = std::env::var("CARGO_MANIFEST_DIR")
-                .unwrap_or_else(|e| panic!("failed to get CARGO_MANIFEST_DIR: {}. This environment variable should be set by Cargo during build.", e));
-            PathBuf::from(crate_root)
-        };
+        // The remaining arguments can be given in any order.
+        let mut file_type = None::<ArgFileType>;
+        let mut sources = None::<Vec<PartialSource>>;
+        let mut layer_report = None::<Ident>;
+        let mut lang_names = None::<Vec<(Ident, LitStr)>>;
+        let mut key_names = None::<Vec<(ArgKeyPath, LitStr)>>;
+        let mut rename_all = None::<RenameRule>;
+        let mut whitespace = None::<Whitespace>;
+        let mut key_whitespace = None::<Vec<(ArgKeyPath, Whitespace)>>;
+        let mut accessors = None::<Ident>;
+        let mut lang_rename = None::<RenameRule>;
+        let mut unused_args = None::<ArgUnusedArgs>;
 
-        let path = if input.peek(kw::lang_file) {
-            parse!(lang_file, syn::LitStr, without_comma);
+        while !input.is_empty() {
+            if input.peek(kw::file_type) {
+                let kw: kw::file_type = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                set_once(&mut file_type, input.parse()?, kw.span, "file_type")?;
+            } else if input.peek(kw::lang_layers) {
+                let kw: kw::lang_layers = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                let content;
+                syn::bracketed!(content in input);
+                let layers = content.parse_terminated(PartialSource::parse, syn::Token![,])?;
+                if layers.is_empty() {
+                    return Err(syn::Error::new(kw.span, "expected at least one layer"));
+                }
+                set_once(
+                    &mut sources,
+                    layers.into_iter().collect(),
+                    kw.span,
+                    "lang_file, lang_folder or lang_layers",
+                )?;
+            } else if input.peek(kw::layer_report) {
+                let kw: kw::layer_report = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                set_once(&mut layer_report, input.parse()?, kw.span, "layer_report")?;
+            } else if input.peek(kw::lang_names) {
+                let kw: kw::lang_names = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                set_once(&mut lang_names, parse_table(input)?, kw.span, "lang_names")?;
+            } else if input.peek(kw::lang_rename) {
+                let kw: kw::lang_rename = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                set_once(&mut lang_rename, input.parse()?, kw.span, "lang_rename")?;
+            } else if input.peek(kw::rename) {
+                let kw: kw::rename = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                set_once(&mut key_names, parse_table(input)?, kw.span, "rename")?;
+            } else if input.peek(kw::rename_all) {
+                let kw: kw::rename_all = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                set_once(&mut rename_all, input.parse()?, kw.span, "rename_all")?;
+            } else if input.peek(kw::whitespace) {
+                let kw: kw::whitespace = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                set_once(&mut whitespace, input.parse()?, kw.span, "whitespace")?;
+            } else if input.peek(kw::key_whitespace) {
+                let kw: kw::key_whitespace = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                let keys = parse_table(input)?;
+                set_once(&mut key_whitespace, keys, kw.span, "key_whitespace")?;
+            } else if input.peek(kw::accessors) {
+                let kw: kw::accessors = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                set_once(&mut accessors, input.parse()?, kw.span, "accessors")?;
+            } else if input.peek(kw::allow_unused_args) {
+                let kw: kw::allow_unused_args = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                set_once(
+                    &mut unused_args,
+                    input.parse()?,
+                    kw.span,
+                    "allow_unused_args",
+                )?;
+            } else {
+                let span = input.span();
+                let Some((path, lit)) = PartialSource::parse_path(input)? else {
+                    return Err(input.error(
+                        "expected file_type, lang_file, lang_folder, lang_layers, layer_report, lang_names, lang_rename, rename, rename_all, whitespace, key_whitespace, accessors or allow_unused_args",
+                    ));
+                };
+                let source = PartialSource {
+                    file_type: None,
+                    path,
+                    lit,
+                };
+                set_once(
+                    &mut sources,
+                    vec![source],
+                    span,
+                    "lang_file, lang_folder or lang_layers",
+                )?;
+            }
 
-            ArgPath::File(crate_root.join(lang_file.value()))
-        } else if input.peek(kw::lang_folder) {
-            parse!(lang_folder, syn::LitStr, without_comma);
+            if input.is_empty() {
+                break;
+            }
+            let _: syn::Token![,] = input.parse()?;
+        }
 
-            ArgPath::Folder(crate_root.join(lang_folder.value()))
-        } else {
-            return Err(input.error("expected lang_file or lang_folder"));
+        let Some(sources) = sources else {
+            return Err(input.error("expected lang_file, lang_folder or lang_layers"));
         };
+        let sources = sources
+            .into_iter()
+            .map(|source| source.resolve(file_type))
+            .collect();
 
-        if input.peek(syn::Token![,]) {
-            let _ = input.parse::<syn::Token![,]>()?;
-        }
+        let lang_names = ArgLangNames {
+            names: lang_names.unwrap_or_default(),
+            rename: lang_rename,
+        };
+        let key_names = ArgKeyNames {
+            names: key_names.unwrap_or_default(),
+            rename_all,
+        };
+        let whitespace = ArgWhitespace {
+            default: whitespace.unwrap_or_default(),
+            keys: key_whitespace.unwrap_or_default(),
+        };
 
         Ok(Self {
             name,
+            is_static,
             lang,
             message,
             supplier,
-            file_type,
-            path,
+            sources,
+            layer_report,
+            lang_names,
+            key_names,
+            whitespace,
+            accessors,
+            unused_args: unused_args.unwrap_or_default(),
         })
     }
 }
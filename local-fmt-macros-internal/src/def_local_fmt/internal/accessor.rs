@@ -0,0 +1,173 @@
+use proc_macro2::TokenStream;
+use syn::Ident;
+
+use crate::{
+    parse::{PlaceholderKind, StaticMessage},
+    utils::hierarchy::Hierarchy,
+};
+
+use super::{
+    arg::{LangMessage, Message, MessageValue},
+    MessageField,
+};
+
+/// The largest number of arguments of `local_format!`, whose `LocalArgs` are implemented for tuples
+/// of up to 12 arguments. The accessors of larger messages format each argument into a `String`.
+const LOCAL_FORMAT_MAX_ARGS: usize = 12;
+
+/// Generates an accessor method for every message of the source language,
+/// documented with the description for translators and the text of the source language.
+///
+/// Messages with arguments are formatted with one `impl Display` parameter per argument,
+/// or `impl NumberArg`, `impl DateArg` and `impl MessageArg` for the arguments declared
+/// as `{0:number}`, `{0:date}` and `{0:msg}`. Messages without arguments return an `impl Display`,
+/// so that they can be loaded into any field type without arguments, such as a `StaticMessage<0>`.
+/// The arguments are written directly into the result, without an intermediate `String` each,
+/// unless the message has more than [`LOCAL_FORMAT_MAX_ARGS`] arguments.
+/// Numbers and booleans are skipped, because the type of their field is not known.
+pub fn to_token(source: &LangMessage, field: &MessageField) -> TokenStream {
+    let mut impls = TokenStream::new();
+    impl_messages(
+        &source.lang,
+        &source.messages,
+        field,
+        &mut Hierarchy::new(),
+        &mut impls,
+    );
+    impls
+}
+
+fn impl_messages(
+    lang: &str,
+    messages: &[Message],
+    field: &MessageField,
+    hierarchy: &mut Hierarchy<String>,
+    impls: &mut TokenStream,
+) {
+    let mut methods = Vec::new();
+    for message in messages {
+        let ident = Ident::new(&message.key, proc_macro2::Span::call_site());
+        match &message.value {
+            MessageValue::Token(token, text) => {
+                let doc = doc(lang, message.description.as_deref(), text);
+                let method = match token.args() {
+                    0 => quote::quote! {
+                        pub fn #ident(&self) -> impl std::fmt::Display + '_ {
+                            local_fmt::local_format_args!(self.#ident)
+                        }
+                    },
+                    _ => {
+                        let args = arg_names(lang, message, token, hierarchy);
+                        let types = (0..token.args()).map(|n| match token.kinds.get(&n) {
+                            Some(PlaceholderKind::Number) => quote::quote! { local_fmt::NumberArg },
+                            Some(PlaceholderKind::Date) => quote::quote! { local_fmt::DateArg },
+                            Some(PlaceholderKind::Message) => {
+                                quote::quote! { local_fmt::MessageArg }
+                            }
+                            Some(PlaceholderKind::Text) | None => {
+                                quote::quote! { std::fmt::Display }
+                            }
+                        });
+                        let body = if token.args() <= LOCAL_FORMAT_MAX_ARGS {
+                            quote::quote! { local_fmt::local_format!(self.#ident, #(#args),*) }
+                        } else {
+                            quote::quote! { self.#ident.format(&[#(&#args.to_string()),*]) }
+                        };
+                        quote::quote! {
+                            #[allow(clippy::too_many_arguments)]
+                            pub fn #ident(&self, #(#args: impl #types),*) -> String {
+                                #body
+                            }
+                        }
+                    }
+                };
+                methods.push(quote::quote! {
+                    #[doc = #doc]
+                    #method
+                });
+            }
+            MessageValue::Nested(nested) => {
+                let nested_field = field
+                    .fields
+                    .iter()
+                    .flatten()
+                    .find(|(name, _)| *name == ident);
+                if let Some((_, nested_field)) = nested_field {
+                    hierarchy.process(message.key.clone(), |hierarchy| {
+                        impl_messages(lang, nested, nested_field, hierarchy, impls)
+                    });
+                }
+            }
+            MessageValue::Text(_) | MessageValue::Scalar(_) => {}
+        }
+    }
+
+    let ty = &field.ty;
+    impls.extend(quote::quote! {
+        impl #ty {
+            #(#methods)*
+        }
+    });
+}
+
+/// Returns the parameter names of a message, taken from its `_args` entry,
+/// then from the names of its placeholders, and `arg0`, `arg1`, ... otherwise.
+fn arg_names(
+    lang: &str,
+    message: &Message,
+    token: &StaticMessage,
+    hierarchy: &Hierarchy<String>,
+) -> Vec<Ident> {
+    let key = hierarchy.join(&message.key);
+    let names = match &message.arg_names {
+        Some(names) => {
+            if names.len() != token.args() {
+                panic!(
+                    "The _args of '{}' in language '{}' name {} arguments, but the message has {}",
+                    key,
+                    lang,
+                    names.len(),
+                    token.args()
+                );
+            }
+            names.clone()
+        }
+        None => (0..token.args())
+            .map(|n| {
+                token
+                    .names
+                    .get(&n)
+                    .cloned()
+                    .unwrap_or_else(|| format!("arg{}", n))
+            })
+            .collect(),
+    };
+
+    let mut idents = Vec::<Ident>::with_capacity(names.len());
+    for name in names {
+        let ident = syn::parse_str::<Ident>(&name).unwrap_or_else(|_| {
+            panic!(
+                "The argument name '{}' of '{}' in language '{}' is not a valid parameter name",
+                name, key, lang
+            )
+        });
+        if idents.contains(&ident) {
+            panic!(
+                "The argument name '{}' of '{}' in language '{}' is used twice",
+                name, key, lang
+            );
+        }
+        idents.push(ident);
+    }
+    idents
+}
+
+fn doc(lang: &str, description: Option<&str>, text: &str) -> String {
+    let mut doc = String::new();
+    if let Some(description) = description {
+        doc.push_str(description);
+        doc.push_str("\n\n");
+    }
+    doc.push_str(&format!("`{}`:\n```text\n{}\n```", lang, text));
+    doc
+}
@@ -17,20 +17,83 @@ pub struct LangMessage {
 pub struct Message {
     pub key: String,
     pub value: MessageValue,
+    /// The description for translators, given in the `_description` table next to the key.
+    pub description: Option<String>,
+    /// The names of the arguments, given in the `_args` table next to the key.
+    pub arg_names: Option<Vec<String>>,
+    /// Whether the message may leave some of the arguments of its field unused,
+    /// set by `allow_unused_args`.
+    pub allow_unused_args: bool,
 }
 
 pub enum MessageValue {
-    Token(StaticMessage),
+    /// A string as found in the file, parsed into a [`MessageValue::Token`] once its key is known.
+    Text(String),
+    /// A parsed message and the text it was parsed from.
+    Token(StaticMessage, String),
+    Scalar(Scalar),
     Nested(Vec<Message>),
 }
 
+/// A number or boolean, converted to the type of the field by `check_scalar_arg`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scalar {
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+}
+
+impl Scalar {
+    /// Returns the value as it is rendered in a `&'static str` field.
+    pub fn text(&self) -> String {
+        match self {
+            Self::Bool(b) => b.to_string(),
+            Self::Int(i) => i.to_string(),
+            // The shortest text that reads back as the same float, with a fraction or an exponent,
+            // so that `2.0` stays `2.0`, whichever parser read the file.
+            Self::Float(f) => format!("{:?}", f),
+        }
+    }
+
+    fn to_token(self, lang: &str, name: &str) -> TokenStream {
+        let text = self.text();
+        let value = match self {
+            Self::Bool(b) => quote::quote! { local_fmt::macros::ScalarValue::Bool(#b) },
+            Self::Int(i) => {
+                let int = proc_macro2::Literal::i128_suffixed(i);
+                let f32 = proc_macro2::Literal::f32_suffixed(i as f32);
+                let f64 = proc_macro2::Literal::f64_suffixed(i as f64);
+                quote::quote! {
+                    local_fmt::macros::ScalarValue::Number { int: Some(#int), f32: #f32, f64: #f64 }
+                }
+            }
+            Self::Float(f) => {
+                if !f.is_finite() {
+                    panic!(
+                        "Expected a finite number for language '{}' and key '{}', but got {}",
+                        lang, name, f
+                    );
+                }
+                let f32 = proc_macro2::Literal::f32_suffixed(f as f32);
+                let f64 = proc_macro2::Literal::f64_suffixed(f);
+                quote::quote! {
+                    local_fmt::macros::ScalarValue::Number { int: None, f32: #f32, f64: #f64 }
+                }
+            }
+        };
+        quote::quote! {
+            local_fmt::macros::Scalar { text: #text, value: #value }
+        }
+    }
+}
+
 impl LangMessage {
-    pub fn to_token(&self, field: &MessageField) -> TokenStream {
+    pub fn to_token(&self, field: &MessageField, is_static: bool) -> TokenStream {
         let lang = Ident::new(&self.lang, proc_macro2::Span::call_site());
         let message = self
             .messages
             .iter()
-            .map(|v| v.to_token(&self.lang, &mut Hierarchy::new(), field));
+            .map(|v| v.to_token(&self.lang, &mut Hierarchy::new(), field, is_static));
         let ty = &field.ty;
         quote::quote! {
             #lang => #ty {
@@ -46,47 +109,164 @@ fn message_token_to_token_stream(
     ident: &Ident,
     lang: &str,
     name: &str,
-    token: &StaticMessage,
+    message: &Message,
+    is_static: bool,
 ) -> TokenStream {
-    let value = match token.placeholder_max {
-        Some(_) => token.to_token_stream(),
-        None => {
-            let value = token.values.iter().fold(String::new(), |mut acc, v| {
-                match v {
-                    StaticMessageValue::StaticText(v) => acc.push_str(v),
-                    _ => {
-                        unreachable!()
-                    }
-                }
-                acc
-            });
-            value.to_token_stream()
+    let (mut ty, mut value, convert) = match &message.value {
+        MessageValue::Token(token, _) => match token.placeholder_max {
+            Some(_) => {
+                let count = token.args();
+                (
+                    quote::quote! { local_fmt::StaticMessage<#count> },
+                    token.to_token_stream(),
+                    quote::quote! { static_message_arg },
+                )
+            }
+            None => {
+                let text = fold_to_str(token);
+                (
+                    quote::quote! { local_fmt::macros::StaticText },
+                    // The message is defined in a const item, as constants such as `{f:RATIO}`
+                    // are not promoted to 'static in the fields.
+                    quote::quote! {
+                        local_fmt::macros::StaticText {
+                            text: #text,
+                            message: {
+                                const MESSAGE: local_fmt::StaticMessage<0> = #token;
+                                MESSAGE
+                            },
+                        }
+                    },
+                    quote::quote! { static_message_arg },
+                )
+            }
+        },
+        MessageValue::Scalar(scalar) => {
+            let scalar = scalar.to_token(lang, name);
+            if !is_static {
+                return quote::quote! {
+                    #ident: check_scalar_arg(#lang, #name, #scalar)
+                };
+            }
+            (
+                quote::quote! { local_fmt::macros::Scalar },
+                scalar,
+                quote::quote! { static_scalar_arg },
+            )
         }
+        MessageValue::Text(_) | MessageValue::Nested(_) => unreachable!(),
     };
+    if message.allow_unused_args && matches!(message.value, MessageValue::Token(..)) {
+        ty = quote::quote! { local_fmt::macros::UnusedArgs<#ty> };
+        value = quote::quote! { local_fmt::macros::UnusedArgs(#value) };
+    }
+    if !is_static {
+        return quote::quote! {
+            #ident: check_static_message_arg(#lang, #name, #value)
+        };
+    }
+    // The value is defined in a const item, so that it is checked at compile time
+    // even though the field is only created when the static is first used.
     quote::quote! {
-        #ident: check_static_message_arg(#lang, #name, #value)
+        #ident: {
+            struct Arg;
+            impl local_fmt::macros::StaticArg for Arg {
+                const LANG: &'static str = #lang;
+                const KEY: &'static str = #name;
+                type Value = #ty;
+                const VALUE: #ty = #value;
+            }
+            #convert::<Arg, _>()
+        }
+    }
+}
+
+/// Folds a message without arguments into a `&'static str`.
+///
+/// A message that only contains text becomes a string literal. A message that uses constants,
+/// such as `{u:MAX}` or `{NAME}`, is formatted by `const_format` into a buffer in nested const items,
+/// whose size is the length of the text plus the maximum length of each constant.
+fn fold_to_str(token: &StaticMessage) -> TokenStream {
+    let text = token
+        .values
+        .iter()
+        .try_fold(String::new(), |mut acc, v| match v {
+            StaticMessageValue::StaticText(v) => {
+                acc.push_str(v);
+                Some(acc)
+            }
+            _ => None,
+        });
+    if let Some(text) = text {
+        return text.to_token_stream();
+    }
+
+    let sizes = token.values.iter().map(|v| match v {
+        StaticMessageValue::StaticText(text) => text.len().to_token_stream(),
+        // The length of u128::MAX, and of i128::MIN with its sign.
+        StaticMessageValue::UNumberIdent(_) => 39usize.to_token_stream(),
+        StaticMessageValue::INumberIdent(_) => 40usize.to_token_stream(),
+        StaticMessageValue::FloatIdent(..) => quote::quote! { local_fmt::FLOAT_STR_LEN },
+        StaticMessageValue::CharIdent(_) => 4usize.to_token_stream(),
+        StaticMessageValue::BoolIdent(_) => 5usize.to_token_stream(),
+        StaticMessageValue::StaticTextIdent(ident) => quote::quote! { #ident.len() },
+        StaticMessageValue::Placeholder(_)
+        | StaticMessageValue::PlaceholderDefault(..)
+        | StaticMessageValue::SectionStart(_)
+        | StaticMessageValue::SectionEnd
+        | StaticMessageValue::KeyRef(_)
+        | StaticMessageValue::Term(_) => unreachable!(),
+    });
+    quote::quote! {
+        {
+            const TEXT: &str = {
+                const SIZE: usize = 0 #(+ #sizes)*;
+                const MESSAGE: local_fmt::StaticMessage<0> = #token;
+                const BUFFER: local_fmt::UtilBufWrapper<SIZE> =
+                    unsafe { MESSAGE.const_format::<SIZE>(&[]) };
+                BUFFER.as_str()
+            };
+            TEXT
+        }
     }
 }
 
 impl Message {
+    pub fn new(key: String, value: MessageValue) -> Self {
+        Self {
+            key,
+            value,
+            description: None,
+            arg_names: None,
+            allow_unused_args: false,
+        }
+    }
+
     fn to_token(
         &self,
         lang: &str,
         hierarchy: &mut Hierarchy<String>,
         field: &MessageField,
+        is_static: bool,
     ) -> TokenStream {
         let name = &self.key;
         let ident = Ident::new(&self.key, proc_macro2::Span::call_site());
         match &field.fields {
             None => match &self.value {
-                MessageValue::Token(token) => {
-                    message_token_to_token_stream(&ident, lang, &hierarchy.join(name), token)
+                MessageValue::Text(_) | MessageValue::Token(..) | MessageValue::Scalar(_) => {
+                    message_token_to_token_stream(
+                        &ident,
+                        lang,
+                        &hierarchy.join(name),
+                        self,
+                        is_static,
+                    )
                 }
                 MessageValue::Nested(messages) => {
                     let mut token_stream = TokenStream::new();
                     for message in messages {
                         let token = hierarchy.process(name.to_string(), |hierarchy| {
-                            message.to_token(lang, hierarchy, field)
+                            message.to_token(lang, hierarchy, field, is_static)
                         });
                         token_stream.extend(quote::quote! {
                             #ident: #token,
@@ -97,8 +277,14 @@ impl Message {
             },
             Some(fields) => match fields.iter().find(|(ty, _)| ty == &ident) {
                 None => match &self.value {
-                    MessageValue::Token(token) => {
-                        message_token_to_token_stream(&ident, lang, &hierarchy.join(name), token)
+                    MessageValue::Text(_) | MessageValue::Token(..) | MessageValue::Scalar(_) => {
+                        message_token_to_token_stream(
+                            &ident,
+                            lang,
+                            &hierarchy.join(name),
+                            self,
+                            is_static,
+                        )
                     }
                     MessageValue::Nested(_) => {
                         panic!(
@@ -110,7 +296,9 @@ impl Message {
                 Some((ident, field)) => {
                     let message = match self.value {
                         MessageValue::Nested(ref messages) => messages,
-                        MessageValue::Token(_) => {
+                        MessageValue::Text(_)
+                        | MessageValue::Token(..)
+                        | MessageValue::Scalar(_) => {
                             panic!(
                                 "Expected a nested message with key {}, but got a string in language {}",
                                 hierarchy.join(name), lang
@@ -120,7 +308,7 @@ impl Message {
                     let token = hierarchy.process(name.to_string(), |hierarchy| {
                         message
                             .iter()
-                            .map(|m| m.to_token(lang, hierarchy, field))
+                            .map(|m| m.to_token(lang, hierarchy, field, is_static))
                             .collect::<Vec<_>>()
                     });
 
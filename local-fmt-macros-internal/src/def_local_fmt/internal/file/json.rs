@@ -1,9 +1,16 @@
-use super::MessageLoader;
+use super::{MessageLoader, Scalar};
 
-pub struct JsonMessageLoader;
+/// The loader of JSON files, whose comments are removed when `COMMENTS` is true.
+pub struct JsonLoader<const COMMENTS: bool>;
 
-impl MessageLoader for JsonMessageLoader {
-    const EXTENSION: &'static str = "json";
+/// The loader of `.json` files.
+pub type JsonMessageLoader = JsonLoader<false>;
+
+/// The loader of `.jsonc` files, JSON with comments.
+pub type JsoncMessageLoader = JsonLoader<true>;
+
+impl<const COMMENTS: bool> MessageLoader for JsonLoader<COMMENTS> {
+    const EXTENSION: &'static str = if COMMENTS { "jsonc" } else { "json" };
 
     type Value = serde_json::Value;
     type NestValue = serde_json::Map<String, serde_json::Value>;
@@ -20,11 +27,79 @@ impl MessageLoader for JsonMessageLoader {
         value.as_str()
     }
 
+    fn value_as_scalar(value: &Self::Value) -> Option<Scalar> {
+        match value {
+            serde_json::Value::Number(n) => Some(match (n.as_i64(), n.as_u64(), n.as_f64()) {
+                (Some(i), _, _) => Scalar::Int(i.into()),
+                (_, Some(u), _) => Scalar::Int(u.into()),
+                (_, _, f) => Scalar::Float(f?),
+            }),
+            serde_json::Value::Bool(b) => Some(Scalar::Bool(*b)),
+            _ => None,
+        }
+    }
+
     fn value_from_str(content: &str) -> Result<Self::Value, String> {
-        serde_json::from_str(content).map_err(|e| e.to_string())
+        let result = if COMMENTS {
+            serde_json::from_str(&strip_comments(content))
+        } else {
+            serde_json::from_str(content)
+        };
+        result.map_err(|e| e.to_string())
     }
 
     fn iter_nested(value: Self::NestValue) -> impl Iterator<Item = (String, Self::Value)> {
         value.into_iter()
     }
 }
+
+/// Replaces the `//` and `/* */` comments of JSON with comments (`.jsonc`) by spaces,
+/// so that the line numbers in parse errors still match the original content.
+fn strip_comments(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            result.push(c);
+            match c {
+                '\\' => result.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                result.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        result.push(c);
+                        break;
+                    }
+                    result.push(' ');
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                result.push_str("  ");
+                let mut last = None;
+                for c in chars.by_ref() {
+                    result.push(if c == '\n' { c } else { ' ' });
+                    if last == Some('*') && c == '/' {
+                        break;
+                    }
+                    last = Some(c);
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
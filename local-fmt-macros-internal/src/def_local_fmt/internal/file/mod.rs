@@ -1,6 +1,14 @@
-use crate::{def_local_fmt::arg::ArgFileType, utils::hierarchy::Hierarchy};
+use std::path::{Path, PathBuf};
 
-use super::{arg::MessageValue, ArgPath, LangMessage};
+use crate::{
+    def_local_fmt::arg::{ArgFileType, ArgPath},
+    utils::hierarchy::Hierarchy,
+};
+
+use super::{
+    arg::{Message, MessageValue, Scalar},
+    LangMessage,
+};
 
 #[cfg(feature = "json")]
 pub mod json;
@@ -11,32 +19,303 @@ pub mod toml;
 #[cfg(feature = "yaml")]
 pub mod yaml;
 
-pub fn parse(file_type: ArgFileType, path: ArgPath) -> Vec<LangMessage> {
-    macro_rules! from_path {
-        ($file_type:ident, $path:ident, {$($pattern:pat => ($feature:literal, $mod:ident::$loader:ident),)+}) => {
-            use ArgFileType::*;
-            match $file_type {
-                $(
-                    $pattern => {
-                        #[cfg(feature = $feature)]
-                        {
-                            $mod::$loader::from_path($path)
-                        }
-                        #[cfg(not(feature = $feature))]
-                        {
-                            panic!(concat!($feature, " feature is not enabled failed to parse {:#?} file"), $path)
-                        }
-                    },
-                )+
+/// The files seen while loading the language files.
+#[derive(Default)]
+pub struct LoadedFiles {
+    /// Every file that was read, so that the generated code can register it as a build dependency.
+    pub read: Vec<PathBuf>,
+    /// Files in a `lang_folder` that were skipped because their file type is unknown.
+    pub ignored: Vec<PathBuf>,
+}
+
+/// Reads a language file and records its path.
+fn read_file(path: &Path, loaded: &mut LoadedFiles) -> String {
+    let content = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+    loaded.read.push(path.to_path_buf());
+    content
+}
+
+/// Returns the entries of the folder sorted by path,
+/// so that the generated code does not depend on the order of `read_dir`.
+fn read_folder(folder: &Path) -> Vec<PathBuf> {
+    let entries = folder.read_dir().unwrap_or_else(|e| {
+        panic!(
+            "Failed to read directory entry in folder {}: {}",
+            folder.display(),
+            e
+        )
+    });
+    let mut paths = entries
+        .map(|entry| {
+            entry
+                .unwrap_or_else(|e| panic!("failed to read entry in {}: {}", folder.display(), e))
+                .path()
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+/// The functions of a [`MessageLoader`], selected by the file type of each file.
+pub struct Loader {
+    pub from_file: fn(&Path, &mut LoadedFiles) -> Vec<LangMessage>,
+    pub messages_from_file:
+        fn(&str, &Path, &mut Hierarchy<String>, &mut LoadedFiles) -> Vec<Message>,
+}
+
+impl Loader {
+    pub fn new<L: MessageLoader>() -> Self {
+        Self {
+            from_file: L::from_file,
+            messages_from_file: L::messages_from_file,
+        }
+    }
+
+    /// Returns the loader of the file type, panicking if its feature is not enabled.
+    pub fn of(file_type: ArgFileType, path: &Path) -> Self {
+        macro_rules! loader {
+            ($feature:literal, $mod:ident::$loader:ident) => {{
+                #[cfg(feature = $feature)]
+                let loader = Some(Self::new::<$mod::$loader>());
+                #[cfg(not(feature = $feature))]
+                let loader = None;
+                loader
+            }};
+        }
+
+        let loader = match file_type {
+            ArgFileType::Toml => loader!("toml", toml::TomlMessageLoader),
+            ArgFileType::Json if path.extension().is_some_and(|e| e == "jsonc") => {
+                loader!("json", json::JsoncMessageLoader)
             }
+            ArgFileType::Json => loader!("json", json::JsonMessageLoader),
+            ArgFileType::Yaml => loader!("yaml", yaml::YamlMessageLoader),
         };
+        loader.unwrap_or_else(|| {
+            panic!(
+                "{} feature is not enabled failed to parse {} file",
+                file_type.name(),
+                path.display()
+            )
+        })
     }
+}
+
+/// Loads the messages of every language from a `lang_file` or a `lang_folder`.
+///
+/// When `file_type` is `None`, the file type of each file is inferred from its extension.
+pub fn parse(
+    file_type: Option<ArgFileType>,
+    path: ArgPath,
+    loaded: &mut LoadedFiles,
+) -> Vec<LangMessage> {
+    match path {
+        ArgPath::File(file) => {
+            let inferred = file.extension().and_then(ArgFileType::from_extension);
+            let file_type = match (file_type, inferred) {
+                (Some(file_type), Some(inferred)) if file_type != inferred => panic!(
+                    "Expected a {} file, but got {} file: {}",
+                    file_type.name(),
+                    inferred.name(),
+                    file.display()
+                ),
+                (Some(file_type), _) | (None, Some(file_type)) => file_type,
+                (None, None) => panic!(
+                    "Failed to infer the file type of {}, specify file_type or use one of the extensions {}",
+                    file.display(),
+                    ArgFileType::EXTENSIONS.join(", ")
+                ),
+            };
+            (Loader::of(file_type, &file).from_file)(&file, loaded)
+        }
+        ArgPath::Folder(folder) => from_folder(&folder, file_type, loaded),
+    }
+}
 
-    from_path! { file_type, path, {
-        Toml => ("toml", toml::TomlMessageLoader),
-        Json => ("json", json::JsonMessageLoader),
-        Yaml => ("yaml", yaml::YamlMessageLoader),
-    } }
+/// An entry of a `lang_folder` that is loaded.
+enum FolderEntry {
+    Folder(String),
+    File(String, ArgFileType),
+}
+
+impl FolderEntry {
+    /// Returns the entry of the path, named after the folder name or the file stem.
+    /// Files of an unknown file type, or of another type than `file_type`, are recorded as ignored.
+    fn new(path: &Path, file_type: Option<ArgFileType>, loaded: &mut LoadedFiles) -> Option<Self> {
+        if path.is_dir() {
+            let name = path
+                .file_name()
+                .unwrap_or_else(|| panic!("failed to get folder name in {}", path.display()));
+            return Some(Self::Folder(name.to_string_lossy().to_string()));
+        }
+
+        let inferred = path.extension().and_then(ArgFileType::from_extension);
+        let Some(inferred) = inferred.filter(|inferred| file_type.map_or(true, |f| f == *inferred))
+        else {
+            loaded.ignored.push(path.to_path_buf());
+            return None;
+        };
+        let stem = path
+            .file_stem()
+            .unwrap_or_else(|| panic!("failed to get file stem in {}", path.display()));
+        Some(Self::File(stem.to_string_lossy().to_string(), inferred))
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Self::Folder(name) | Self::File(name, _) => name,
+        }
+    }
+
+    fn load(
+        &self,
+        lang: &str,
+        path: &Path,
+        file_type: Option<ArgFileType>,
+        hierarchy: &mut Hierarchy<String>,
+        loaded: &mut LoadedFiles,
+    ) -> Vec<Message> {
+        match self {
+            Self::Folder(_) => from_namespace_folder(lang, path, file_type, hierarchy, loaded),
+            Self::File(_, file_type) => {
+                (Loader::of(*file_type, path).messages_from_file)(lang, path, hierarchy, loaded)
+            }
+        }
+    }
+}
+
+/// Loads a folder that contains either one file per language (`EN.toml`)
+/// or one folder per language (`EN/common.toml`), or both.
+fn from_folder(
+    folder: &Path,
+    file_type: Option<ArgFileType>,
+    loaded: &mut LoadedFiles,
+) -> Vec<LangMessage> {
+    let mut lang_messages = Vec::<LangMessage>::new();
+    for path in read_folder(folder) {
+        let Some(entry) = FolderEntry::new(&path, file_type, loaded) else {
+            continue;
+        };
+        let lang = entry.name().to_string();
+        let messages = entry.load(&lang, &path, file_type, &mut Hierarchy::new(), loaded);
+
+        match lang_messages.iter_mut().find(|l| l.lang == lang) {
+            Some(lang_message) => merge_messages(
+                &lang,
+                &mut lang_message.messages,
+                messages,
+                &mut Hierarchy::new(),
+                &path,
+            ),
+            None => lang_messages.push(LangMessage { lang, messages }),
+        }
+    }
+    lang_messages
+}
+
+/// Loads a language folder, where each file or subfolder becomes
+/// a nested message named after its file stem.
+fn from_namespace_folder(
+    lang: &str,
+    folder: &Path,
+    file_type: Option<ArgFileType>,
+    hierarchy: &mut Hierarchy<String>,
+    loaded: &mut LoadedFiles,
+) -> Vec<Message> {
+    let mut messages = Vec::new();
+    for path in read_folder(folder) {
+        let Some(entry) = FolderEntry::new(&path, file_type, loaded) else {
+            continue;
+        };
+        let key = entry.name().to_string();
+        if key == TERMS_KEY && !hierarchy.as_vec().is_empty() {
+            panic!(
+                "The {} table of language '{}' must be at the root of the language, but was found at '{}' in {}",
+                TERMS_KEY,
+                lang,
+                hierarchy.join(&key),
+                path.display()
+            );
+        }
+        let nested = hierarchy.process(key.clone(), |hierarchy| {
+            entry.load(lang, &path, file_type, hierarchy, loaded)
+        });
+        let nested = vec![Message::new(key, MessageValue::Nested(nested))];
+        merge_messages(lang, &mut messages, nested, hierarchy, &path);
+    }
+    messages
+}
+
+/// Merges `from` into `into`, merging nested messages recursively.
+/// Panics if both define the same message.
+fn merge_messages(
+    lang: &str,
+    into: &mut Vec<Message>,
+    from: Vec<Message>,
+    hierarchy: &mut Hierarchy<String>,
+    path: &Path,
+) {
+    for message in from {
+        let Some(existing) = into.iter_mut().find(|m| m.key == message.key) else {
+            into.push(message);
+            continue;
+        };
+        match (&mut existing.value, message.value) {
+            (MessageValue::Nested(existing), MessageValue::Nested(nested)) => {
+                hierarchy.process(message.key, |hierarchy| {
+                    merge_messages(lang, existing, nested, hierarchy, path)
+                });
+            }
+            _ => panic!(
+                "Duplicate key '{}' in language '{}': {} defines a key that is already defined",
+                hierarchy.join(&message.key),
+                lang,
+                path.display()
+            ),
+        }
+    }
+}
+
+/// The reserved key of the table that describes the messages next to it for translators.
+const DESCRIPTION_KEY: &str = "_description";
+/// The reserved key of the table that names the arguments of the messages next to it,
+/// as a comma-separated list such as `"command, options"`.
+const ARGS_KEY: &str = "_args";
+
+/// The reserved key of the table of terms of a language, such as product names,
+/// which can be used in its messages as `{$term}`.
+/// It is kept as a nested message until [`super::generate`] takes it out.
+pub const TERMS_KEY: &str = "_terms";
+
+/// Returns the message that an entry of the reserved table `table` refers to.
+fn annotated<'a>(
+    messages: &'a mut [Message],
+    table: &str,
+    key: &str,
+    lang: &str,
+    hierarchy: &Hierarchy<String>,
+) -> &'a mut Message {
+    let message = messages
+        .iter_mut()
+        .find(|m| m.key == key)
+        .unwrap_or_else(|| {
+            panic!(
+                "The entry '{}' of {} for language '{}' does not match any message",
+                hierarchy.join(key),
+                table,
+                lang
+            )
+        });
+    if let MessageValue::Nested(_) = message.value {
+        panic!(
+            "The entry '{}' of {} for language '{}' refers to a nested message, only messages can be annotated",
+            hierarchy.join(key),
+            table,
+            lang
+        );
+    }
+    message
 }
 
 pub trait MessageLoader: Sized {
@@ -48,34 +327,14 @@ pub trait MessageLoader: Sized {
 
     fn value_to_nest(value: Self::Value) -> Option<Self::NestValue>;
     fn value_as_str(value: &Self::Value) -> Option<&str>;
+    /// Returns the number or boolean of the value, which is loaded as a [`Scalar`].
+    fn value_as_scalar(value: &Self::Value) -> Option<Scalar>;
     fn value_from_str(content: &str) -> Result<Self::Value, String>;
     fn iter_nested(value: Self::NestValue) -> impl Iterator<Item = (String, Self::Value)>;
 
-    fn from_path(path: ArgPath) -> Vec<LangMessage> {
-        match path {
-            ArgPath::File(file) => Self::from_file(file),
-            ArgPath::Folder(folder) => Self::from_folder(folder),
-        }
-    }
-
-    fn from_file(file: std::path::PathBuf) -> Vec<LangMessage> {
-        let extension = file.extension().unwrap_or_else(|| {
-            panic!(
-                "Failed to retrieve file extension for path: {}",
-                file.display()
-            )
-        });
-        if extension != Self::EXTENSION {
-            panic!(
-                "Expected a {} file, but got {} file: {}",
-                Self::EXTENSION,
-                extension.to_string_lossy(),
-                file.display()
-            )
-        }
-
-        let content = std::fs::read_to_string(&file)
-            .unwrap_or_else(|e| panic!("failed to read {}: {}", file.display(), e));
+    /// Reads and parses a file whose root must be a nest value.
+    fn nest_from_file(file: &Path, loaded: &mut LoadedFiles) -> Self::NestValue {
+        let content = read_file(file, loaded);
 
         let value = Self::value_from_str(&content).unwrap_or_else(|e| {
             panic!(
@@ -86,14 +345,19 @@ pub trait MessageLoader: Sized {
             )
         });
 
-        let nest = Self::value_to_nest(value).unwrap_or_else(|| {
+        Self::value_to_nest(value).unwrap_or_else(|| {
             panic!(
                 "Expected a {} in the {} file: {}",
                 Self::NEST_VALUE_NAME,
                 Self::EXTENSION,
                 file.display()
             )
-        });
+        })
+    }
+
+    /// Loads a `lang_file`, whose top-level keys are the languages.
+    fn from_file(file: &Path, loaded: &mut LoadedFiles) -> Vec<LangMessage> {
+        let nest = Self::nest_from_file(file, loaded);
 
         let mut lang_messages = Vec::new();
         for (lang, value) in Self::iter_nested(nest) {
@@ -111,86 +375,66 @@ pub trait MessageLoader: Sized {
         lang_messages
     }
 
-    fn from_folder(folder: std::path::PathBuf) -> Vec<LangMessage> {
-        let files = folder.read_dir().unwrap_or_else(|e| {
-            panic!(
-                "Failed to read directory entry in folder {}: {}",
-                folder.display(),
-                e
-            )
-        });
-        let mut lang_messages = Vec::new();
-        for entry in files {
-            let entry =
-                entry.unwrap_or_else(|e| panic!("failed to read entry in {}: {}", folder.display(), e));
-            let path = entry.path();
-            let exn = path.extension().unwrap_or_else(|| {
-                panic!(
-                    "Failed to retrieve file extension for path: {}",
-                    path.display()
-                )
-            });
-            if exn != Self::EXTENSION {
-                continue;
-            }
-            let lang = path
-                .file_stem()
-                .unwrap_or_else(|| panic!("failed to get file stem in {}", path.display()))
-                .to_string_lossy();
-
-            let content = std::fs::read_to_string(&path)
-                .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
-            let value = Self::value_from_str(&content).unwrap_or_else(|e| {
-                panic!(
-                    "failed to parse {} in {}: {}",
-                    Self::EXTENSION,
-                    path.display(),
-                    e
-                )
-            });
-
-            let nest = Self::value_to_nest(value).unwrap_or_else(|| {
-                panic!(
-                    "Expected a {} in the {} file: {}",
-                    Self::NEST_VALUE_NAME,
-                    Self::EXTENSION,
-                    path.display()
-                )
-            });
-
-            let messages = Self::internal(&lang, &mut Hierarchy::new(), nest);
-            lang_messages.push(LangMessage {
-                lang: lang.to_string(),
-                messages,
-            });
-        }
-        lang_messages
+    /// Loads a file in a `lang_folder`, which contains the messages of a single language.
+    fn messages_from_file(
+        lang: &str,
+        file: &Path,
+        hierarchy: &mut Hierarchy<String>,
+        loaded: &mut LoadedFiles,
+    ) -> Vec<Message> {
+        let nest = Self::nest_from_file(file, loaded);
+        Self::internal(lang, hierarchy, nest)
     }
 
     fn internal(
         lang: &str,
         hierarchy: &mut Hierarchy<String>,
         value: Self::NestValue,
-    ) -> Vec<super::Message> {
+    ) -> Vec<Message> {
         let mut messages = Vec::new();
+        let mut descriptions = Vec::new();
+        let mut args = Vec::new();
         for (key, value) in Self::iter_nested(value) {
-            if let Some(value) = Self::value_as_str(&value) {
-                messages.push(super::Message {
-                    value: MessageValue::Token(value.parse().unwrap_or_else(|e| {
-                        let key = hierarchy.join(value);
+            match key.as_str() {
+                DESCRIPTION_KEY => {
+                    descriptions = Self::string_table(lang, hierarchy, &key, value);
+                    continue;
+                }
+                ARGS_KEY => {
+                    args = Self::string_table(lang, hierarchy, &key, value);
+                    continue;
+                }
+                TERMS_KEY => {
+                    if !hierarchy.as_vec().is_empty() {
                         panic!(
-                            "Failed to parse message token for language '{}' and key '{}': {}",
-                            lang, key, e
-                        )
-                    })),
-                    key,
-                });
+                            "The {} table of language '{}' must be at the root of the language, but was found at '{}'",
+                            TERMS_KEY,
+                            lang,
+                            hierarchy.join(&key)
+                        );
+                    }
+                    let terms = Self::string_table(lang, hierarchy, &key, value)
+                        .into_iter()
+                        .map(|(term, text)| Message::new(term, MessageValue::Text(text)))
+                        .collect();
+                    messages.push(Message::new(key, MessageValue::Nested(terms)));
+                    continue;
+                }
+                // The other keys starting with `_` are messages like any other key.
+                _ => {}
+            }
+            if let Some(value) = Self::value_as_str(&value) {
+                messages.push(Message::new(key, MessageValue::Text(value.to_string())));
+                continue;
+            }
+            if let Some(scalar) = Self::value_as_scalar(&value) {
+                messages.push(Message::new(key, MessageValue::Scalar(scalar)));
                 continue;
             }
             let nest = Self::value_to_nest(value).unwrap_or_else(|| {
                 let display_key = hierarchy.join(&key);
                 panic!(
-                    "Expected a string or {} for language '{}' and key '{}'",
+                    "Expected a string, number, boolean or {} for language '{}' and key '{}'",
                     Self::NEST_VALUE_NAME,
                     lang,
                     display_key
@@ -199,11 +443,40 @@ pub trait MessageLoader: Sized {
             let temp_key = key.clone();
             let nest_messages =
                 hierarchy.process(temp_key, |hierarchy| Self::internal(lang, hierarchy, nest));
-            messages.push(super::Message {
-                key,
-                value: MessageValue::Nested(nest_messages),
-            });
+            messages.push(Message::new(key, MessageValue::Nested(nest_messages)));
+        }
+
+        for (key, description) in descriptions {
+            let message = annotated(&mut messages, DESCRIPTION_KEY, &key, lang, hierarchy);
+            message.description = Some(description);
+        }
+        for (key, names) in args {
+            let message = annotated(&mut messages, ARGS_KEY, &key, lang, hierarchy);
+            let names = names.split(',').map(|name| name.trim().to_string());
+            message.arg_names = Some(names.collect());
         }
         messages
     }
+
+    /// Reads a reserved table whose values are all strings, such as `_description` or `_args`.
+    fn string_table(
+        lang: &str,
+        hierarchy: &Hierarchy<String>,
+        name: &str,
+        value: Self::Value,
+    ) -> Vec<(String, String)> {
+        let expected = format!(
+            "Expected a {} of strings for language '{}' and key '{}'",
+            Self::NEST_VALUE_NAME,
+            lang,
+            hierarchy.join(name)
+        );
+        let nest = Self::value_to_nest(value).unwrap_or_else(|| panic!("{}", expected));
+        Self::iter_nested(nest)
+            .map(|(key, value)| match Self::value_as_str(&value) {
+                Some(value) => (key, value.to_string()),
+                None => panic!("{}", expected),
+            })
+            .collect()
+    }
 }
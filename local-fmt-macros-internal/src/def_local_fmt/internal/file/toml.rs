@@ -1,4 +1,4 @@
-use super::MessageLoader;
+use super::{MessageLoader, Scalar};
 
 pub struct TomlMessageLoader;
 
@@ -20,6 +20,15 @@ impl MessageLoader for TomlMessageLoader {
         value.as_str()
     }
 
+    fn value_as_scalar(value: &Self::Value) -> Option<Scalar> {
+        match value {
+            toml::Value::Integer(i) => Some(Scalar::Int((*i).into())),
+            toml::Value::Float(f) => Some(Scalar::Float(*f)),
+            toml::Value::Boolean(b) => Some(Scalar::Bool(*b)),
+            _ => None,
+        }
+    }
+
     fn value_from_str(content: &str) -> Result<Self::Value, String> {
         toml::from_str(content).map_err(|e| e.to_string())
     }
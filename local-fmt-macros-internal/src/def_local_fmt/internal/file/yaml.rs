@@ -1,4 +1,4 @@
-use super::MessageLoader;
+use super::{MessageLoader, Scalar};
 
 pub struct YamlMessageLoader;
 
@@ -17,6 +17,15 @@ impl MessageLoader for YamlMessageLoader {
         value.as_str()
     }
 
+    fn value_as_scalar(value: &Self::Value) -> Option<Scalar> {
+        match value {
+            yaml_rust::Yaml::Integer(i) => Some(Scalar::Int((*i).into())),
+            yaml_rust::Yaml::Real(_) => value.as_f64().map(Scalar::Float),
+            yaml_rust::Yaml::Boolean(b) => Some(Scalar::Bool(*b)),
+            _ => None,
+        }
+    }
+
     fn value_from_str(content: &str) -> Result<Self::Value, String> {
         yaml_rust::YamlLoader::load_from_str(content)
             .map_err(|v| v.to_string())
@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use crate::{parse::PlaceholderKind, utils::hierarchy::Hierarchy};
+
+use super::arg::{LangMessage, Message, MessageValue};
+
+/// The kinds of the arguments of each message, with the language that declared each kind.
+type Declared = BTreeMap<String, BTreeMap<usize, (PlaceholderKind, String)>>;
+
+/// Checks the kinds of the arguments declared such as `{0:number}`.
+///
+/// The kinds are declared in the `source` language of the accessors, which take values of these kinds.
+/// The other languages may repeat the kind of an argument, but declaring another kind,
+/// or a kind that the source does not declare, is an error.
+/// A placeholder without a kind inherits the kind of the argument declared by another language.
+/// Without accessors, the kinds are advisory, and are only checked not to conflict between the languages.
+pub fn check(lang_messages: &[LangMessage], source: Option<&str>) {
+    let mut declared = Declared::new();
+    let (sources, others) = lang_messages
+        .iter()
+        .partition::<Vec<_>, _>(|lang_message| Some(lang_message.lang.as_str()) == source);
+    for lang_message in sources.into_iter().chain(others) {
+        collect(
+            &lang_message.lang,
+            &lang_message.messages,
+            &mut Hierarchy::new(),
+            source,
+            &mut declared,
+        );
+    }
+}
+
+fn collect(
+    lang: &str,
+    messages: &[Message],
+    hierarchy: &mut Hierarchy<String>,
+    source: Option<&str>,
+    declared: &mut Declared,
+) {
+    for message in messages {
+        match &message.value {
+            MessageValue::Token(token, _) => {
+                let key = hierarchy.join(&message.key);
+                let kinds = declared.entry(key.clone()).or_default();
+                for (n, kind) in &token.kinds {
+                    match (kinds.get(n), source) {
+                        (Some((existing, other)), _) if existing != kind => panic!(
+                            "The argument {} of '{}' is a {} in language '{}', but a {} in language '{}'",
+                            n, key, existing, other, kind, lang
+                        ),
+                        (Some(_), _) => {}
+                        (None, Some(source)) if source != lang => panic!(
+                            "The argument {} of '{}' is a {} in language '{}', but has no kind in language '{}' of the accessors, declare the kind in '{}'",
+                            n, key, kind, lang, source, source
+                        ),
+                        (None, _) => {
+                            kinds.insert(*n, (*kind, lang.to_string()));
+                        }
+                    }
+                }
+            }
+            MessageValue::Nested(nested) => hierarchy.process(message.key.clone(), |hierarchy| {
+                collect(lang, nested, hierarchy, source, declared)
+            }),
+            MessageValue::Text(_) | MessageValue::Scalar(_) => {}
+        }
+    }
+}
@@ -1,14 +1,125 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
 use arg::{LangMessage, Message, MessageValue};
 
-use crate::utils::hierarchy::Hierarchy;
+use crate::{parse::StaticMessage, utils::hierarchy::Hierarchy};
 
-use super::arg::{ArgFileType, ArgPath, MessageField};
+use super::arg::{
+    ArgKeyNames, ArgLangNames, ArgSource, ArgUnusedArgs, ArgWhitespace, MessageField,
+};
 
+pub mod accessor;
 mod arg;
 pub mod file;
+pub mod kind;
+pub mod reference;
+pub mod term;
+
+pub struct LoadedMessages {
+    pub lang_messages: Vec<LangMessage>,
+    /// Every file that was read while loading the messages.
+    pub files: Vec<PathBuf>,
+    /// Files in a `lang_folder` that were skipped because their file type is unknown.
+    pub ignored: Vec<PathBuf>,
+    /// The source that supplied each message, sorted by language and key.
+    pub layers: Vec<MessageLayer>,
+}
+
+pub struct MessageLayer {
+    pub lang: String,
+    pub key: String,
+    pub layer: usize,
+    pub source: String,
+}
+
+pub fn generate(
+    sources: Vec<ArgSource>,
+    lang_names: &ArgLangNames,
+    key_names: &ArgKeyNames,
+    whitespace: &ArgWhitespace,
+    unused_args: &ArgUnusedArgs,
+    message: &MessageField,
+    accessors: Option<&syn::Ident>,
+) -> LoadedMessages {
+    let mut loaded = file::LoadedFiles::default();
+    let mut lang_messages = Vec::<LangMessage>::new();
+    let mut layers = BTreeMap::<(String, String), (usize, String)>::new();
+    let mut terms = BTreeMap::<String, BTreeMap<String, String>>::new();
+    let mut used_terms = BTreeMap::<String, BTreeSet<String>>::new();
+
+    for (layer, source) in sources.into_iter().enumerate() {
+        for mut lang_message in file::parse(source.file_type, source.path, &mut loaded) {
+            let variant = lang_names.variant(&lang_message.lang);
+            if syn::parse_str::<syn::Ident>(&variant).is_err() {
+                panic!(
+                    "The language '{}' of {} is not a valid variant name, map it to a variant with lang_names or lang_rename",
+                    variant, source.name
+                );
+            }
+            lang_message.lang = variant;
+            term::take(
+                &lang_message.lang,
+                &mut lang_message.messages,
+                terms.entry(lang_message.lang.clone()).or_default(),
+            );
+            rename_keys(
+                &lang_message.lang,
+                &mut lang_message.messages,
+                &mut Hierarchy::new(),
+                key_names,
+                &source.name,
+            );
+            parse_texts(
+                &lang_message.lang,
+                &mut lang_message.messages,
+                &mut Hierarchy::new(),
+                whitespace,
+                unused_args,
+            );
 
-pub fn generate(file_type: ArgFileType, path: ArgPath, message: &MessageField) -> Vec<LangMessage> {
-    let lang_messages = file::parse(file_type, path);
+            term::collect(
+                &lang_message.messages,
+                used_terms.entry(lang_message.lang.clone()).or_default(),
+            );
+
+            for_each_key(&lang_message.messages, &mut Hierarchy::new(), &mut |key| {
+                let entry = (layer, source.name.clone());
+                layers.insert((lang_message.lang.clone(), key), entry);
+            });
+
+            match lang_messages
+                .iter_mut()
+                .find(|l| l.lang == lang_message.lang)
+            {
+                Some(base) => override_messages(
+                    &base.lang,
+                    &mut base.messages,
+                    lang_message.messages,
+                    &mut Hierarchy::new(),
+                    &source.name,
+                ),
+                None => lang_messages.push(lang_message),
+            }
+        }
+    }
+
+    for lang_message in &mut lang_messages {
+        let lang_terms = terms.remove(&lang_message.lang).unwrap_or_default();
+        let used = used_terms.remove(&lang_message.lang).unwrap_or_default();
+        term::substitute(
+            &lang_message.lang,
+            &mut lang_message.messages,
+            &lang_terms,
+            &used,
+        );
+        reference::resolve(&lang_message.lang, &mut lang_message.messages);
+    }
+
+    let source = accessors.map(ToString::to_string);
+    kind::check(&lang_messages, source.as_deref());
 
     for lang_message in &lang_messages {
         check_lang_message(
@@ -19,7 +130,161 @@ pub fn generate(file_type: ArgFileType, path: ArgPath, message: &MessageField) -
         );
     }
 
-    lang_messages
+    LoadedMessages {
+        lang_messages,
+        files: loaded.read,
+        ignored: loaded.ignored,
+        layers: layers
+            .into_iter()
+            .map(|((lang, key), (layer, source))| MessageLayer {
+                lang,
+                key,
+                layer,
+                source,
+            })
+            .collect(),
+    }
+}
+
+/// Renames the keys found in the files to the fields of the `message` struct.
+fn rename_keys(
+    lang: &str,
+    messages: &mut [Message],
+    hierarchy: &mut Hierarchy<String>,
+    key_names: &ArgKeyNames,
+    source: &str,
+) {
+    let mut fields = Vec::<(String, String)>::with_capacity(messages.len());
+    for message in messages {
+        let field = key_names
+            .field(hierarchy.as_vec(), &message.key)
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Invalid key '{}' in language '{}' of {}: {}, map it to a field with rename",
+                    hierarchy.join(&message.key),
+                    lang,
+                    source,
+                    e
+                )
+            });
+        if syn::parse_str::<syn::Ident>(&field).is_err() {
+            panic!(
+                "The key '{}' in language '{}' of {} is not a valid field name, map it to a field with rename or rename_all",
+                hierarchy.join(&message.key),
+                lang,
+                source
+            );
+        }
+        if let Some((key, _)) = fields.iter().find(|(_, f)| *f == field) {
+            panic!(
+                "The keys '{}' and '{}' in language '{}' of {} both map to the field '{}'",
+                hierarchy.join(key),
+                hierarchy.join(&message.key),
+                lang,
+                source,
+                field
+            );
+        }
+        fields.push((message.key.clone(), field.clone()));
+
+        if let MessageValue::Nested(nested) = &mut message.value {
+            hierarchy.process(field.clone(), |hierarchy| {
+                rename_keys(lang, nested, hierarchy, key_names, source)
+            });
+        }
+        message.key = field;
+    }
+}
+
+/// Applies the whitespace options to the strings found in the files and parses them into messages.
+fn parse_texts(
+    lang: &str,
+    messages: &mut [Message],
+    hierarchy: &mut Hierarchy<String>,
+    whitespace: &ArgWhitespace,
+    unused_args: &ArgUnusedArgs,
+) {
+    for message in messages {
+        match &mut message.value {
+            MessageValue::Text(text) => {
+                let path = hierarchy.process(message.key.clone(), |h| h.as_vec().clone());
+                let text = whitespace.for_key(&path).apply(text);
+                message.allow_unused_args = unused_args.for_key(&path);
+                let token = StaticMessage::parse_with_unused_args(&text, message.allow_unused_args)
+                    .unwrap_or_else(|e| {
+                        panic!(
+                            "Failed to parse message token for language '{}' and key '{}': {}",
+                            lang,
+                            hierarchy.join(&message.key),
+                            e
+                        )
+                    });
+                message.value = MessageValue::Token(token, text);
+            }
+            MessageValue::Nested(nested) => hierarchy.process(message.key.clone(), |hierarchy| {
+                parse_texts(lang, nested, hierarchy, whitespace, unused_args)
+            }),
+            MessageValue::Token(..) | MessageValue::Scalar(_) => {}
+        }
+    }
+}
+
+/// Calls `f` with the full key of every message that is not nested.
+fn for_each_key(
+    messages: &[Message],
+    hierarchy: &mut Hierarchy<String>,
+    f: &mut impl FnMut(String),
+) {
+    for message in messages {
+        match &message.value {
+            MessageValue::Text(_) | MessageValue::Token(..) | MessageValue::Scalar(_) => {
+                f(hierarchy.join(&message.key))
+            }
+            MessageValue::Nested(nested) => hierarchy.process(message.key.clone(), |hierarchy| {
+                for_each_key(nested, hierarchy, f);
+            }),
+        }
+    }
+}
+
+/// Overrides the messages of earlier layers with the messages of `layer`, key by key.
+fn override_messages(
+    lang: &str,
+    base: &mut Vec<Message>,
+    layer: Vec<Message>,
+    hierarchy: &mut Hierarchy<String>,
+    source: &str,
+) {
+    for message in layer {
+        let Some(existing) = base.iter_mut().find(|m| m.key == message.key) else {
+            base.push(message);
+            continue;
+        };
+        if message.description.is_some() {
+            existing.description = message.description;
+        }
+        if message.arg_names.is_some() {
+            existing.arg_names = message.arg_names;
+        }
+        match (&mut existing.value, message.value) {
+            (MessageValue::Nested(existing), MessageValue::Nested(nested)) => {
+                hierarchy.process(message.key, |hierarchy| {
+                    override_messages(lang, existing, nested, hierarchy, source)
+                });
+            }
+            (existing, value @ (MessageValue::Token(..) | MessageValue::Scalar(_)))
+                if !matches!(existing, MessageValue::Nested(_)) =>
+            {
+                *existing = value
+            }
+            _ => panic!(
+                "The layer '{}' cannot override the key '{}' in language '{}': a message and a nested message cannot override each other",
+                source,
+                hierarchy.join(&message.key),
+                lang
+            ),
+        }
+    }
 }
 
 fn check_lang_message(
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    parse::{MessageValueError, PlaceholderKind, StaticMessage, StaticMessageValue},
+    utils::hierarchy::Hierarchy,
+};
+
+use super::arg::{Message, MessageValue};
+
+/// Replaces the key references such as `{@words.ownership}` with the referenced messages
+/// of the same language, so that the placeholders of the referenced messages
+/// become placeholders of the referencing message, along with their names and kinds.
+pub fn resolve(lang: &str, messages: &mut [Message]) {
+    let mut leaves = BTreeMap::new();
+    collect(messages, &mut Hierarchy::new(), &mut leaves);
+
+    let mut resolver = Resolver {
+        lang,
+        leaves: &leaves,
+        resolved: BTreeMap::new(),
+        stack: Vec::new(),
+    };
+    replace(messages, &mut Hierarchy::new(), &mut resolver);
+}
+
+/// The values of a message, with the names and kinds declared by its placeholders.
+#[derive(Clone, Default)]
+struct Leaf {
+    values: Vec<StaticMessageValue>,
+    names: BTreeMap<usize, String>,
+    kinds: BTreeMap<usize, PlaceholderKind>,
+}
+
+impl Leaf {
+    /// Adds the values of a referenced message, whose placeholders keep their numbers,
+    /// failing if one of them is named or declared differently.
+    fn extend(&mut self, referenced: Leaf) -> Result<(), MessageValueError> {
+        self.values.extend(referenced.values);
+        for (n, name) in referenced.names {
+            match self.names.get(&n) {
+                Some(existing) if *existing != name => {
+                    return Err(MessageValueError::ConflictingNames(
+                        n,
+                        existing.clone(),
+                        name,
+                    ))
+                }
+                _ => {
+                    self.names.insert(n, name);
+                }
+            }
+        }
+        for (n, kind) in referenced.kinds {
+            match self.kinds.get(&n) {
+                Some(&existing) if existing != kind => {
+                    return Err(MessageValueError::ConflictingKinds(n, existing, kind))
+                }
+                _ => {
+                    self.kinds.insert(n, kind);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Collects the values of every message that is not nested, by its full key.
+fn collect(
+    messages: &[Message],
+    hierarchy: &mut Hierarchy<String>,
+    leaves: &mut BTreeMap<String, Leaf>,
+) {
+    for message in messages {
+        let key = hierarchy.join(&message.key);
+        match &message.value {
+            MessageValue::Token(token, _) => {
+                let leaf = Leaf {
+                    values: token.values.clone(),
+                    names: token.names.clone(),
+                    kinds: token.kinds.clone(),
+                };
+                leaves.insert(key, leaf);
+            }
+            MessageValue::Scalar(scalar) => {
+                let leaf = Leaf {
+                    values: vec![StaticMessageValue::StaticText(scalar.text())],
+                    ..Leaf::default()
+                };
+                leaves.insert(key, leaf);
+            }
+            MessageValue::Nested(nested) => hierarchy.process(message.key.clone(), |hierarchy| {
+                collect(nested, hierarchy, leaves)
+            }),
+            MessageValue::Text(_) => {}
+        }
+    }
+}
+
+fn replace(messages: &mut [Message], hierarchy: &mut Hierarchy<String>, resolver: &mut Resolver) {
+    for message in messages {
+        match &mut message.value {
+            MessageValue::Token(token, _) => {
+                if token.values.iter().all(|v| v.as_key_ref().is_none()) {
+                    continue;
+                }
+                let key = hierarchy.join(&message.key);
+                let leaf = resolver.resolve(&key);
+                *token = StaticMessage::with_unused_args(leaf.values, message.allow_unused_args)
+                    .unwrap_or_else(|e| resolver.fail(&key, e));
+                token.names = leaf.names;
+                token.kinds = leaf.kinds;
+            }
+            MessageValue::Nested(nested) => hierarchy.process(message.key.clone(), |hierarchy| {
+                replace(nested, hierarchy, resolver)
+            }),
+            MessageValue::Text(_) | MessageValue::Scalar(_) => {}
+        }
+    }
+}
+
+struct Resolver<'a> {
+    lang: &'a str,
+    leaves: &'a BTreeMap<String, Leaf>,
+    resolved: BTreeMap<String, Leaf>,
+    /// The keys being resolved, to detect cycles.
+    stack: Vec<String>,
+}
+
+impl Resolver<'_> {
+    fn fail(&self, key: &str, error: MessageValueError) -> ! {
+        panic!(
+            "Failed to resolve the key references of '{}' in language '{}': {}",
+            key, self.lang, error
+        )
+    }
+
+    fn resolve(&mut self, key: &str) -> Leaf {
+        if let Some(leaf) = self.resolved.get(key) {
+            return leaf.clone();
+        }
+        if self.stack.iter().any(|k| k == key) {
+            let mut cycle = self.stack.clone();
+            cycle.push(key.to_string());
+            panic!(
+                "The key references in language '{}' form a cycle: {}",
+                self.lang,
+                cycle.join(" -> ")
+            );
+        }
+
+        let leaves = self.leaves;
+        let leaf = leaves.get(key).unwrap_or_else(|| {
+            panic!(
+                "The key '{}' referenced by '{}' in language '{}' is not a message",
+                key,
+                self.stack.last().map_or("", String::as_str),
+                self.lang
+            )
+        });
+
+        self.stack.push(key.to_string());
+        let mut resolved = Leaf {
+            values: Vec::with_capacity(leaf.values.len()),
+            names: leaf.names.clone(),
+            kinds: leaf.kinds.clone(),
+        };
+        for value in &leaf.values {
+            match value {
+                StaticMessageValue::KeyRef(target) => {
+                    let referenced = self.resolve(target);
+                    if let Err(e) = resolved.extend(referenced) {
+                        self.fail(key, e);
+                    }
+                }
+                value => resolved.values.push(value.clone()),
+            }
+        }
+        self.stack.pop();
+
+        self.resolved.insert(key.to_string(), resolved.clone());
+        resolved
+    }
+}
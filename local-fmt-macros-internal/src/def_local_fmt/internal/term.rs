@@ -0,0 +1,109 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{parse::StaticMessageValue, utils::hierarchy::Hierarchy};
+
+use super::{
+    arg::{Message, MessageValue},
+    file::TERMS_KEY,
+};
+
+/// Takes the `_terms` table out of the messages of a language, adding its terms to `terms`.
+pub fn take(lang: &str, messages: &mut Vec<Message>, terms: &mut BTreeMap<String, String>) {
+    let Some(index) = messages.iter().position(|m| m.key == TERMS_KEY) else {
+        return;
+    };
+    let MessageValue::Nested(table) = messages.remove(index).value else {
+        panic!(
+            "Expected a table of strings for the {} of language '{}'",
+            TERMS_KEY, lang
+        );
+    };
+    for term in table {
+        let MessageValue::Text(text) = term.value else {
+            panic!(
+                "Expected a string for the term '{}' of language '{}'",
+                term.key, lang
+            );
+        };
+        terms.insert(term.key, text);
+    }
+}
+
+/// Records the terms used by the messages of a layer in `used`.
+///
+/// This is done before the messages are overridden by later layers,
+/// so that a term used only by an overridden message still counts as used.
+pub fn collect(messages: &[Message], used: &mut BTreeSet<String>) {
+    for message in messages {
+        match &message.value {
+            MessageValue::Token(token, _) => {
+                for value in &token.values {
+                    if let StaticMessageValue::Term(term) = value {
+                        used.insert(term.clone());
+                    }
+                }
+            }
+            MessageValue::Nested(nested) => collect(nested, used),
+            MessageValue::Text(_) | MessageValue::Scalar(_) => {}
+        }
+    }
+}
+
+/// Replaces the terms such as `{$product}` with their text,
+/// failing if a term is not defined or if a defined term is never used.
+///
+/// `used` holds the terms used by every layer, as recorded by [`collect`].
+pub fn substitute(
+    lang: &str,
+    messages: &mut [Message],
+    terms: &BTreeMap<String, String>,
+    used: &BTreeSet<String>,
+) {
+    replace(lang, messages, &mut Hierarchy::new(), terms);
+
+    let unused = terms
+        .keys()
+        .filter(|term| !used.contains(term.as_str()))
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    if !unused.is_empty() {
+        panic!(
+            "The terms {} of language '{}' are not used by any message",
+            unused.join(", "),
+            lang
+        );
+    }
+}
+
+fn replace(
+    lang: &str,
+    messages: &mut [Message],
+    hierarchy: &mut Hierarchy<String>,
+    terms: &BTreeMap<String, String>,
+) {
+    for message in messages {
+        match &mut message.value {
+            MessageValue::Token(token, _) => {
+                for value in &mut token.values {
+                    let StaticMessageValue::Term(term) = value else {
+                        continue;
+                    };
+                    let Some(text) = terms.get(term.as_str()) else {
+                        panic!(
+                            "The term '{}' used by '{}' is not defined in the {} of language '{}'",
+                            term,
+                            hierarchy.join(&message.key),
+                            TERMS_KEY,
+                            lang
+                        );
+                    };
+                    *value = StaticMessageValue::StaticText(text.clone());
+                }
+            }
+            MessageValue::Nested(nested) => hierarchy.process(message.key.clone(), |hierarchy| {
+                replace(lang, nested, hierarchy, terms)
+            }),
+            MessageValue::Text(_) | MessageValue::Scalar(_) => {}
+        }
+    }
+}
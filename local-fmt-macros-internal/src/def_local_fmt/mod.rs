@@ -7,28 +7,137 @@ pub use arg::Args;
 use proc_macro2::TokenStream;
 
 pub fn generate(args: Args) -> syn::Result<TokenStream> {
-    let lang_messages = internal::generate(args.file_type, args.path, &args.message);
-    let internal_tokens = lang_messages
+    let loaded = internal::generate(
+        args.sources,
+        &args.lang_names,
+        &args.key_names,
+        &args.whitespace,
+        &args.unused_args,
+        &args.message,
+        args.accessors.as_ref(),
+    );
+    let internal_tokens = loaded
+        .lang_messages
         .iter()
-        .map(|lang_message| lang_message.to_token(&args.message))
+        .map(|lang_message| lang_message.to_token(&args.message, args.is_static))
         .collect::<Vec<_>>();
+    let accessors = match &args.accessors {
+        Some(source) => {
+            let lang_message = loaded
+                .lang_messages
+                .iter()
+                .find(|lang_message| *source == lang_message.lang)
+                .ok_or_else(|| {
+                    syn::Error::new(
+                        source.span(),
+                        format!("no messages were found for the language {}", source),
+                    )
+                })?;
+            Some(internal::accessor::to_token(lang_message, &args.message))
+        }
+        None => None,
+    };
     let name = args.name;
     let lang = args.lang;
     let message = args.message.ty;
     let supplier = args.supplier;
-    let token = quote::quote! {
-        pub const #name: local_fmt::LocalFmt<#lang, #message, {<#lang as enum_table::Enumable>::COUNT}> = {
-            use local_fmt::macros::check_static_message_arg;
-
-            let messages = enum_table::et!(#lang, #message, |lang| match lang {
-                #(
-                    #lang::#internal_tokens,
-                )*
-                #[allow(unreachable_patterns, clippy::panic)]
-                _ => panic!("Not filled all languages"),
+    let files = loaded.files.iter().map(|file| {
+        let file = file
+            .to_str()
+            .unwrap_or_else(|| panic!("Expected a UTF-8 path, but got {}", file.display()));
+        quote::quote! {
+            const _: &[u8] = include_bytes!(#file);
+        }
+    });
+    let mut not_filled = String::from("Not filled all languages");
+    let ignored_files = (!loaded.ignored.is_empty()).then(|| {
+        let ignored = loaded
+            .ignored
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let note = format!(
+            "The following files were ignored because their file type is unknown or differs from file_type: {}",
+            ignored
+        );
+        not_filled.push_str(". ");
+        not_filled.push_str(&note);
+        // Proc macros cannot emit warnings on stable, so the ignored files are reported
+        // by the use of a deprecated item, which can be silenced with `#[allow(deprecated)]`.
+        quote::quote! {
+            const _: () = {
+                #[deprecated(note = #note)]
+                #[allow(non_upper_case_globals)]
+                const ignored_files: () = ();
+                ignored_files
+            };
+        }
+    });
+    let layer_report = args.layer_report.map(|report| {
+        let layers = loaded.layers.iter().map(|layer| {
+            let internal::MessageLayer {
+                lang,
+                key,
+                layer,
+                source,
+            } = layer;
+            quote::quote! {
+                local_fmt::MessageLayer {
+                    lang: #lang,
+                    key: #key,
+                    layer: #layer,
+                    source: #source,
+                }
+            }
+        });
+        quote::quote! {
+            pub const #report: &[local_fmt::MessageLayer] = &[
+                #(#layers,)*
+            ];
+        }
+    });
+    let body = quote::quote! {
+        // Makes cargo rebuild this crate when one of the language files changes.
+        #(#files)*
+
+        let messages = enum_table::et!(#lang, #message, |lang| match lang {
+            #(
+                #lang::#internal_tokens,
+            )*
+            #[allow(unreachable_patterns, clippy::panic)]
+            _ => panic!("{}", #not_filled),
+        });
+        local_fmt::LocalFmt::new(messages, #supplier)
+    };
+    let ty = quote::quote! {
+        local_fmt::LocalFmt<#lang, #message, {<#lang as enum_table::Enumable>::COUNT}>
+    };
+    let definition = if args.is_static {
+        quote::quote! {
+            pub static #name: std::sync::LazyLock<#ty> = std::sync::LazyLock::new(|| {
+                use local_fmt::macros::{static_message_arg, static_scalar_arg};
+
+                #body
             });
-            local_fmt::LocalFmt::new(messages, #supplier)
-        };
+        }
+    } else {
+        quote::quote! {
+            pub const #name: #ty = {
+                use local_fmt::macros::{check_scalar_arg, check_static_message_arg};
+
+                #body
+            };
+        }
+    };
+    let token = quote::quote! {
+        #ignored_files
+
+        #layer_report
+
+        #accessors
+
+        #definition
     };
 
     Ok(token)
@@ -10,6 +10,9 @@ pub type AllocMessage = MessageToken<AllocMessageValue>;
 pub enum AllocMessageValue {
     AllocText(String),
     Placeholder(usize),
+    PlaceholderDefault(usize, String),
+    SectionStart(usize),
+    SectionEnd,
     AllocTextIdent(Ident),
 }
 
@@ -19,7 +22,9 @@ impl MessageValue for AllocMessageValue {
 
     fn as_arg(&self) -> Option<usize> {
         match self {
-            AllocMessageValue::Placeholder(n) => Some(*n),
+            AllocMessageValue::Placeholder(n)
+            | AllocMessageValue::PlaceholderDefault(n, _)
+            | AllocMessageValue::SectionStart(n) => Some(*n),
             _ => None,
         }
     }
@@ -28,16 +33,29 @@ impl MessageValue for AllocMessageValue {
         Self::AllocText(s)
     }
 
+    fn new_placeholder_default(n: usize, default: String) -> Self {
+        Self::PlaceholderDefault(n, default)
+    }
+
+    fn new_section_start(n: usize) -> Self {
+        Self::SectionStart(n)
+    }
+
+    fn new_section_end() -> Self {
+        Self::SectionEnd
+    }
+
     fn new_placeholder_raw(s: &str) -> Result<Self, super::MessageValueError> {
+        if s.starts_with('@') || s.starts_with('$') {
+            return Err(super::MessageValueError::FileOnly(s.to_string()));
+        }
         let number = s.parse::<usize>();
         match number {
             Ok(ok) => Ok(Self::Placeholder(ok)),
             Err(err) if IntErrorKind::InvalidDigit == *err.kind() => Ok(Self::AllocTextIdent(
                 Ident::new(s, proc_macro2::Span::call_site()),
             )),
-            Err(err) => {
-                panic!("Invalid placeholder on {{{}}}: {}", s, err);
-            }
+            Err(_) => Err(super::MessageValueError::NumberOverflow(s.to_string())),
         }
     }
 }
@@ -56,6 +74,23 @@ impl ToTokens for AllocMessageValue {
                     local_fmt::AllocMessageFormat::Placeholder(#n),
                 });
             }
+            AllocMessageValue::PlaceholderDefault(n, default) => {
+                let n = *n;
+                tokens.extend(quote::quote! {
+                    local_fmt::AllocMessageFormat::PlaceholderDefault(#n, #default.to_string()),
+                });
+            }
+            AllocMessageValue::SectionStart(n) => {
+                let n = *n;
+                tokens.extend(quote::quote! {
+                    local_fmt::AllocMessageFormat::SectionStart(#n),
+                });
+            }
+            AllocMessageValue::SectionEnd => {
+                tokens.extend(quote::quote! {
+                    local_fmt::AllocMessageFormat::SectionEnd,
+                });
+            }
             AllocMessageValue::AllocTextIdent(ident) => {
                 tokens.extend(quote::quote! {
                     local_fmt::AllocMessageFormat::AllocText(#ident),
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr};
 
 use proc_macro2::TokenStream;
 use quote::ToTokens;
@@ -16,6 +16,63 @@ pub enum MessageValueError {
     NotFound(usize, usize),
     #[error("not found placeholder value in braces")]
     EmptyPlaceholder,
+    #[error("Placeholder number {0} is named both '{1}' and '{2}'")]
+    ConflictingNames(usize, String, String),
+    #[error("The placeholder {{{0}}} can only be used in the files of def_local_fmt")]
+    FileOnly(String),
+    #[error("The placeholder {{{0}}} must be written as {{f.N:IDENT}}, where N is a precision from 0 to 255")]
+    InvalidPrecision(String),
+    #[error("Unknown kind '{1}' of placeholder number {0}, expected number, date, text or msg")]
+    UnknownKind(usize, String),
+    #[error("Placeholder number {0} is declared both as {1} and {2}")]
+    ConflictingKinds(usize, PlaceholderKind, PlaceholderKind),
+    #[error("The placeholder {{{0}}} has a default text, but only an argument such as {{0|default}} can have one")]
+    InvalidDefault(String),
+    #[error("A conditional section opened with {{{0}? is not closed with }}")]
+    UnclosedSection(usize),
+    #[error("The placeholder number {0} is too large")]
+    NumberOverflow(String),
+}
+
+/// The kind of a placeholder, declared as `{0:number}`, `{1:date}`, `{2:text}` or `{3:msg}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceholderKind {
+    Number,
+    Date,
+    Text,
+    /// Another message formatted with its own arguments.
+    Message,
+}
+
+impl PlaceholderKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Number => "number",
+            Self::Date => "date",
+            Self::Text => "text",
+            Self::Message => "msg",
+        }
+    }
+}
+
+impl std::fmt::Display for PlaceholderKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for PlaceholderKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "number" => Ok(Self::Number),
+            "date" => Ok(Self::Date),
+            "text" => Ok(Self::Text),
+            "msg" => Ok(Self::Message),
+            _ => Err(()),
+        }
+    }
 }
 
 pub trait MessageValue: ToTokens + Sized {
@@ -24,14 +81,32 @@ pub trait MessageValue: ToTokens + Sized {
 
     fn as_arg(&self) -> Option<usize>;
 
+    /// Returns true for a reference to another message, whose placeholders are not known yet.
+    fn is_key_ref(&self) -> bool {
+        false
+    }
+
     fn new_string(s: String) -> Self;
 
     fn new_placeholder_raw(s: &str) -> Result<Self, MessageValueError>;
+
+    /// Creates a placeholder written as `{1|anonymous}`, rendered as `default` if the argument is empty.
+    fn new_placeholder_default(n: usize, default: String) -> Self;
+
+    /// Creates the start of a conditional section written as `{1?`, rendered if the argument is not empty.
+    fn new_section_start(n: usize) -> Self;
+
+    /// Creates the `}` that ends a conditional section.
+    fn new_section_end() -> Self;
 }
 
 pub struct MessageToken<V: MessageValue> {
     pub values: Vec<V>,
     pub placeholder_max: Option<usize>,
+    /// The names given to placeholders written as `{0=name}`.
+    pub names: BTreeMap<usize, String>,
+    /// The kinds declared by placeholders written as `{0:number}`.
+    pub kinds: BTreeMap<usize, PlaceholderKind>,
 }
 
 impl<V: MessageValue> MessageToken<V> {
@@ -40,9 +115,21 @@ impl<V: MessageValue> MessageToken<V> {
     }
 
     pub fn new(values: Vec<V>) -> Result<Self, MessageValueError> {
+        Self::with_unused_args(values, false)
+    }
+
+    /// Creates a message like [`MessageToken::new`], but with `allow_unused_args`,
+    /// the placeholders below the highest one may be left unused,
+    /// for the languages that omit an argument of a message of `def_local_fmt`.
+    pub fn with_unused_args(
+        values: Vec<V>,
+        allow_unused_args: bool,
+    ) -> Result<Self, MessageValueError> {
         let max = values.iter().filter_map(|v| v.as_arg()).max();
 
-        if let Some(max) = max {
+        // The placeholders are checked once the key references are replaced.
+        let has_key_ref = values.iter().any(V::is_key_ref);
+        if let Some(max) = max.filter(|_| !has_key_ref && !allow_unused_args) {
             let mut flag = vec![false; max + 1];
             for v in &values {
                 if let Some(n) = v.as_arg() {
@@ -59,6 +146,8 @@ impl<V: MessageValue> MessageToken<V> {
         Ok(Self {
             values,
             placeholder_max: max,
+            names: BTreeMap::new(),
+            kinds: BTreeMap::new(),
         })
     }
 }
@@ -91,14 +180,29 @@ impl<V: MessageValue> FromStr for MessageToken<V> {
     type Err = MessageValueError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with_unused_args(s, false)
+    }
+}
+
+impl<V: MessageValue> MessageToken<V> {
+    /// Parses a message like [`FromStr`], allowing unused placeholders like [`MessageToken::with_unused_args`].
+    pub fn parse_with_unused_args(
+        s: &str,
+        allow_unused_args: bool,
+    ) -> Result<Self, MessageValueError> {
         let mut values = Vec::<V>::new();
+        let mut names = BTreeMap::<usize, String>::new();
+        let mut kinds = BTreeMap::<usize, PlaceholderKind>::new();
 
         let mut buffer = Vec::<u8>::new();
+        // The numbers of the conditional sections that are not closed yet.
+        let mut sections = Vec::<usize>::new();
 
-        let mut bytes = s.bytes();
+        let mut bytes = s.bytes().peekable();
 
         while let Some(byte) = bytes.next() {
             match byte {
+                b'{' if bytes.next_if_eq(&b'{').is_some() => buffer.push(b'{'),
                 b'{' => {
                     if !buffer.is_empty() {
                         let s = unsafe { String::from_utf8_unchecked(std::mem::take(&mut buffer)) };
@@ -106,20 +210,62 @@ impl<V: MessageValue> FromStr for MessageToken<V> {
                     }
 
                     let mut placeholder = Vec::new();
+                    let mut default = None::<Vec<u8>>;
 
                     loop {
                         match bytes.next() {
-                            Some(byte) => match byte {
-                                b'}' => {
+                            Some(byte) => match (byte, &mut default) {
+                                (b'}', _) => {
                                     if placeholder.is_empty() {
                                         return Err(MessageValueError::EmptyPlaceholder);
                                     }
                                     let placeholder =
                                         unsafe { std::str::from_utf8_unchecked(&placeholder) };
-                                    values.push(V::new_placeholder_raw(placeholder)?);
+                                    let number = split_kind(placeholder, &mut kinds)?;
+                                    let number = split_name(number, &mut names)?;
+                                    match default {
+                                        Some(default) => {
+                                            let n = number.parse::<usize>().map_err(|_| {
+                                                if number.bytes().all(|b| b.is_ascii_digit()) {
+                                                    MessageValueError::NumberOverflow(
+                                                        number.to_string(),
+                                                    )
+                                                } else {
+                                                    MessageValueError::InvalidDefault(
+                                                        placeholder.to_string(),
+                                                    )
+                                                }
+                                            })?;
+                                            let default =
+                                                unsafe { String::from_utf8_unchecked(default) };
+                                            values.push(V::new_placeholder_default(n, default));
+                                        }
+                                        None => values.push(V::new_placeholder_raw(number)?),
+                                    }
+                                    break;
+                                }
+                                (b'?', None)
+                                    if !placeholder.is_empty()
+                                        && placeholder.iter().all(u8::is_ascii_digit) =>
+                                {
+                                    let number =
+                                        unsafe { std::str::from_utf8_unchecked(&placeholder) };
+                                    let n = number.parse::<usize>().map_err(|_| {
+                                        MessageValueError::NumberOverflow(number.to_string())
+                                    })?;
+                                    sections.push(n);
+                                    values.push(V::new_section_start(n));
                                     break;
                                 }
-                                byte => placeholder.push(byte),
+                                (b'|', None) => default = Some(Vec::new()),
+                                (b'\\', Some(default)) => {
+                                    match bytes.next_if(|b| matches!(b, b'{' | b'}' | b'\\')) {
+                                        Some(byte) => default.push(byte),
+                                        None => default.push(b'\\'),
+                                    }
+                                }
+                                (byte, Some(default)) => default.push(byte),
+                                (byte, None) => placeholder.push(byte),
                             },
                             None => {
                                 return Err(MessageValueError::EmptyPlaceholder);
@@ -127,28 +273,89 @@ impl<V: MessageValue> FromStr for MessageToken<V> {
                         }
                     }
                 }
-                b'\\' => {
-                    if let Some(byte) = bytes.next() {
-                        match byte {
-                            b'{' => buffer.push(b'{'),
-                            _ => {
-                                buffer.push(b'\\');
-                                buffer.push(byte);
-                            }
-                        }
-                    } else {
-                        buffer.push(b'\\');
+                b'}' if !sections.is_empty() => {
+                    if !buffer.is_empty() {
+                        let s = unsafe { String::from_utf8_unchecked(std::mem::take(&mut buffer)) };
+                        values.push(V::new_string(s));
                     }
+                    sections.pop();
+                    values.push(V::new_section_end());
                 }
+                b'}' if bytes.next_if_eq(&b'}').is_some() => buffer.push(b'}'),
+                b'\\' => match bytes.next_if(|b| matches!(b, b'{' | b'}' | b'\\')) {
+                    Some(byte) => buffer.push(byte),
+                    None => buffer.push(b'\\'),
+                },
                 _ => buffer.push(byte),
             }
         }
 
+        if let Some(n) = sections.first() {
+            return Err(MessageValueError::UnclosedSection(*n));
+        }
+
         if !buffer.is_empty() {
             let s = unsafe { String::from_utf8_unchecked(buffer) };
             values.push(V::new_string(s));
         }
 
-        Self::new(values)
+        let mut token = Self::with_unused_args(values, allow_unused_args)?;
+        token.names = names;
+        token.kinds = kinds;
+        Ok(token)
+    }
+}
+
+/// Splits the kind off a placeholder written as `{0:number}` or `{0=count:number}`,
+/// recording it in `kinds`.
+fn split_kind<'a>(
+    placeholder: &'a str,
+    kinds: &mut BTreeMap<usize, PlaceholderKind>,
+) -> Result<&'a str, MessageValueError> {
+    let Some((rest, kind)) = placeholder.rsplit_once(':') else {
+        return Ok(placeholder);
+    };
+    let number = rest.split_once('=').map_or(rest, |(number, _)| number);
+    let Ok(n) = number.parse::<usize>() else {
+        return Ok(placeholder);
+    };
+    let kind = kind
+        .parse::<PlaceholderKind>()
+        .map_err(|()| MessageValueError::UnknownKind(n, kind.to_string()))?;
+    match kinds.get(&n) {
+        Some(existing) if *existing != kind => {
+            Err(MessageValueError::ConflictingKinds(n, *existing, kind))
+        }
+        _ => {
+            kinds.insert(n, kind);
+            Ok(rest)
+        }
+    }
+}
+
+/// Splits the name off a placeholder written as `{0=name}`, recording it in `names`.
+fn split_name<'a>(
+    placeholder: &'a str,
+    names: &mut BTreeMap<usize, String>,
+) -> Result<&'a str, MessageValueError> {
+    let Some((number, name)) = placeholder.split_once('=') else {
+        return Ok(placeholder);
+    };
+    let Ok(n) = number.parse::<usize>() else {
+        return Ok(placeholder);
+    };
+    if name.is_empty() {
+        return Err(MessageValueError::EmptyPlaceholder);
+    }
+    match names.get(&n) {
+        Some(existing) if existing != name => Err(MessageValueError::ConflictingNames(
+            n,
+            existing.clone(),
+            name.to_string(),
+        )),
+        _ => {
+            names.insert(n, name.to_string());
+            Ok(number)
+        }
     }
 }
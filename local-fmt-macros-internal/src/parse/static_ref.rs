@@ -7,12 +7,46 @@ use super::{MessageToken, MessageValue};
 
 pub type StaticMessage = MessageToken<StaticMessageValue>;
 
+#[derive(Clone)]
 pub enum StaticMessageValue {
     StaticText(String),
     UNumberIdent(Ident),
     INumberIdent(Ident),
+    /// A float constant such as `{f:RATIO}`, or `{f.2:RATIO}` with two digits after the point.
+    FloatIdent(Ident, Option<u8>),
+    CharIdent(Ident),
+    BoolIdent(Ident),
     Placeholder(usize),
+    /// A placeholder with a default text, such as `{1|anonymous}`.
+    PlaceholderDefault(usize, String),
+    /// The start of a conditional section, such as `{1? ({1})}`.
+    SectionStart(usize),
+    SectionEnd,
     StaticTextIdent(Ident),
+    /// A reference to another key of the same language, such as `{@words.ownership}`,
+    /// which is replaced by that message in `def_local_fmt`.
+    KeyRef(String),
+    /// A term of the `_terms` table of the language, such as `{$product}`,
+    /// which is replaced by its text in `def_local_fmt`.
+    Term(String),
+}
+
+impl StaticMessageValue {
+    pub fn as_key_ref(&self) -> Option<&str> {
+        match self {
+            StaticMessageValue::KeyRef(key) => Some(key),
+            _ => None,
+        }
+    }
+
+    /// Returns the placeholder as written, if it can only be used in the files of `def_local_fmt`.
+    pub fn file_only(&self) -> Option<String> {
+        match self {
+            StaticMessageValue::KeyRef(key) => Some(format!("@{}", key)),
+            StaticMessageValue::Term(term) => Some(format!("${}", term)),
+            _ => None,
+        }
+    }
 }
 
 impl MessageValue for StaticMessageValue {
@@ -21,17 +55,45 @@ impl MessageValue for StaticMessageValue {
 
     fn as_arg(&self) -> Option<usize> {
         match self {
-            StaticMessageValue::Placeholder(n) => Some(*n),
+            StaticMessageValue::Placeholder(n)
+            | StaticMessageValue::PlaceholderDefault(n, _)
+            | StaticMessageValue::SectionStart(n) => Some(*n),
             _ => None,
         }
     }
 
+    fn is_key_ref(&self) -> bool {
+        self.as_key_ref().is_some()
+    }
+
     fn new_string(s: String) -> Self {
         Self::StaticText(s)
     }
 
+    fn new_placeholder_default(n: usize, default: String) -> Self {
+        Self::PlaceholderDefault(n, default)
+    }
+
+    fn new_section_start(n: usize) -> Self {
+        Self::SectionStart(n)
+    }
+
+    fn new_section_end() -> Self {
+        Self::SectionEnd
+    }
+
     fn new_placeholder_raw(s: &str) -> Result<Self, super::MessageValueError> {
-        if let Some(ident) = s.strip_prefix("u:") {
+        if let Some(key) = s.strip_prefix('@') {
+            if key.is_empty() {
+                return Err(super::MessageValueError::EmptyPlaceholder);
+            }
+            Ok(Self::KeyRef(key.to_string()))
+        } else if let Some(term) = s.strip_prefix('$') {
+            if term.is_empty() {
+                return Err(super::MessageValueError::EmptyPlaceholder);
+            }
+            Ok(Self::Term(term.to_string()))
+        } else if let Some(ident) = s.strip_prefix("u:") {
             Ok(Self::UNumberIdent(Ident::new(
                 ident,
                 proc_macro2::Span::call_site(),
@@ -41,6 +103,32 @@ impl MessageValue for StaticMessageValue {
                 ident,
                 proc_macro2::Span::call_site(),
             )))
+        } else if let Some(ident) = s.strip_prefix("f:") {
+            Ok(Self::FloatIdent(
+                Ident::new(ident, proc_macro2::Span::call_site()),
+                None,
+            ))
+        } else if let Some(rest) = s.strip_prefix("f.") {
+            let (precision, ident) = rest
+                .split_once(':')
+                .ok_or_else(|| super::MessageValueError::InvalidPrecision(s.to_string()))?;
+            let precision = precision
+                .parse::<u8>()
+                .map_err(|_| super::MessageValueError::InvalidPrecision(s.to_string()))?;
+            Ok(Self::FloatIdent(
+                Ident::new(ident, proc_macro2::Span::call_site()),
+                Some(precision),
+            ))
+        } else if let Some(ident) = s.strip_prefix("c:") {
+            Ok(Self::CharIdent(Ident::new(
+                ident,
+                proc_macro2::Span::call_site(),
+            )))
+        } else if let Some(ident) = s.strip_prefix("b:") {
+            Ok(Self::BoolIdent(Ident::new(
+                ident,
+                proc_macro2::Span::call_site(),
+            )))
         } else {
             let number = s.parse::<usize>();
             match number {
@@ -48,9 +136,7 @@ impl MessageValue for StaticMessageValue {
                 Err(err) if IntErrorKind::InvalidDigit == *err.kind() => Ok(Self::StaticTextIdent(
                     Ident::new(s, proc_macro2::Span::call_site()),
                 )),
-                Err(err) => {
-                    panic!("Invalid placeholder on {{{}}}: {}", s, err);
-                }
+                Err(_) => Err(super::MessageValueError::NumberOverflow(s.to_string())),
             }
         }
     }
@@ -74,17 +160,56 @@ impl ToTokens for StaticMessageValue {
                     local_fmt::RefMessageFormat::INumber(#ident as i128),
                 });
             }
+            StaticMessageValue::FloatIdent(ident, precision) => {
+                let precision = match precision {
+                    Some(precision) => quote::quote! { ::core::option::Option::Some(#precision) },
+                    None => quote::quote! { ::core::option::Option::None },
+                };
+                tokens.extend(quote::quote! {
+                    local_fmt::RefMessageFormat::Float(local_fmt::ConstFloat::new(#ident, #precision)),
+                });
+            }
+            StaticMessageValue::CharIdent(ident) => {
+                tokens.extend(quote::quote! {
+                    local_fmt::RefMessageFormat::Char(#ident),
+                });
+            }
+            StaticMessageValue::BoolIdent(ident) => {
+                tokens.extend(quote::quote! {
+                    local_fmt::RefMessageFormat::Bool(#ident),
+                });
+            }
             StaticMessageValue::Placeholder(n) => {
                 let n = *n;
                 tokens.extend(quote::quote! {
                     local_fmt::RefMessageFormat::Placeholder(#n),
                 });
             }
+            StaticMessageValue::PlaceholderDefault(n, default) => {
+                let n = *n;
+                tokens.extend(quote::quote! {
+                    local_fmt::RefMessageFormat::PlaceholderDefault(#n, #default),
+                });
+            }
+            StaticMessageValue::SectionStart(n) => {
+                let n = *n;
+                tokens.extend(quote::quote! {
+                    local_fmt::RefMessageFormat::SectionStart(#n),
+                });
+            }
+            StaticMessageValue::SectionEnd => {
+                tokens.extend(quote::quote! {
+                    local_fmt::RefMessageFormat::SectionEnd,
+                });
+            }
             StaticMessageValue::StaticTextIdent(ident) => {
                 tokens.extend(quote::quote! {
                     local_fmt::RefMessageFormat::RefText(#ident),
                 });
             }
+            StaticMessageValue::KeyRef(_) | StaticMessageValue::Term(_) => {
+                unreachable!("key references and terms are replaced before generating the message")
+            }
         }
     }
 }
@@ -4,7 +4,9 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{parse::Parse, punctuated::Punctuated, spanned::Spanned, LitStr};
 
-use crate::parse::{AllocMessage, StaticMessage};
+use crate::parse::{
+    AllocMessage, MessageToken, MessageValue, MessageValueError, StaticMessage, StaticMessageValue,
+};
 
 pub struct Args {
     pub texts: Punctuated<LitStr, syn::Token![,]>,
@@ -27,12 +29,41 @@ impl Args {
 
         if is_static {
             StaticMessage::from_str(&text)
+                .and_then(
+                    |v| match v.values.iter().find_map(StaticMessageValue::file_only) {
+                        Some(placeholder) => Err(MessageValueError::FileOnly(placeholder)),
+                        None => file_only_declarations(v),
+                    },
+                )
                 .map_err(|v| syn::Error::new(self.texts.span(), v))
                 .map(|v| v.into_token_stream())
         } else {
             AllocMessage::from_str(&text)
+                .and_then(file_only_declarations)
                 .map_err(|v| syn::Error::new(self.texts.span(), v))
                 .map(|v| v.into_token_stream())
         }
     }
 }
+
+/// Fails if a placeholder is named as `{0=name}` or has a kind as `{0:number}`,
+/// which only the files of `def_local_fmt` can declare.
+fn file_only_declarations<V: MessageValue>(
+    token: MessageToken<V>,
+) -> Result<MessageToken<V>, MessageValueError> {
+    let n = match (token.names.keys().next(), token.kinds.keys().next()) {
+        (Some(name), Some(kind)) => *name.min(kind),
+        (Some(n), None) | (None, Some(n)) => *n,
+        (None, None) => return Ok(token),
+    };
+    let mut placeholder = n.to_string();
+    if let Some(name) = token.names.get(&n) {
+        placeholder.push('=');
+        placeholder.push_str(name);
+    }
+    if let Some(kind) = token.kinds.get(&n) {
+        placeholder.push(':');
+        placeholder.push_str(kind.name());
+    }
+    Err(MessageValueError::FileOnly(placeholder))
+}
@@ -0,0 +1,144 @@
+/// A serde-style rule to convert a name between naming conventions.
+///
+/// The name is split into words at `-`, `_`, spaces and lowercase-to-uppercase boundaries,
+/// which are then joined in the convention of the rule.
+///
+/// # Example
+/// ```rust
+/// use local_fmt_macros_internal::utils::case::RenameRule;
+///
+/// assert_eq!(RenameRule::PascalCase.apply("en-US"), "EnUs");
+/// assert_eq!(RenameRule::SnakeCase.apply("signIn"), "sign_in");
+/// assert_eq!(RenameRule::KebabCase.apply("sign_in"), "sign-in");
+/// assert_eq!(RenameRule::UpperCase.apply("ja"), "JA");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRule {
+    /// `lowercase`, words are joined without a separator.
+    LowerCase,
+    /// `UPPERCASE`, words are joined without a separator.
+    UpperCase,
+    /// `PascalCase`
+    PascalCase,
+    /// `camelCase`
+    CamelCase,
+    /// `snake_case`
+    SnakeCase,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnakeCase,
+    /// `kebab-case`
+    KebabCase,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    pub const NAMES: &'static [&'static str] = &[
+        "lowercase",
+        "UPPERCASE",
+        "PascalCase",
+        "camelCase",
+        "snake_case",
+        "SCREAMING_SNAKE_CASE",
+        "kebab-case",
+        "SCREAMING-KEBAB-CASE",
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lowercase" => Some(Self::LowerCase),
+            "UPPERCASE" => Some(Self::UpperCase),
+            "PascalCase" => Some(Self::PascalCase),
+            "camelCase" => Some(Self::CamelCase),
+            "snake_case" => Some(Self::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Some(Self::ScreamingSnakeCase),
+            "kebab-case" => Some(Self::KebabCase),
+            "SCREAMING-KEBAB-CASE" => Some(Self::ScreamingKebabCase),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of this rule, as written in the macro arguments.
+    pub fn name(self) -> &'static str {
+        Self::NAMES[self as usize]
+    }
+
+    /// Converts the name to the convention of this rule.
+    pub fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        let capitalize = |word: &str| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect()
+            })
+        };
+
+        match self {
+            Self::LowerCase => words.concat().to_lowercase(),
+            Self::UpperCase => words.concat().to_uppercase(),
+            Self::PascalCase => words.iter().map(|w| capitalize(w)).collect(),
+            Self::CamelCase => words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| {
+                    if i == 0 {
+                        w.to_lowercase()
+                    } else {
+                        capitalize(w)
+                    }
+                })
+                .collect(),
+            Self::SnakeCase => words.join("_").to_lowercase(),
+            Self::ScreamingSnakeCase => words.join("_").to_uppercase(),
+            Self::KebabCase => words.join("-").to_lowercase(),
+            Self::ScreamingKebabCase => words.join("-").to_uppercase(),
+        }
+    }
+}
+
+impl syn::parse::Parse for RenameRule {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit: syn::LitStr = input.parse()?;
+        Self::from_name(&lit.value()).ok_or_else(|| {
+            syn::Error::new(
+                lit.span(),
+                format!("expected one of {}", Self::NAMES.join(", ")),
+            )
+        })
+    }
+}
+
+fn split_words(name: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = None::<usize>;
+    let mut prev = None::<char>;
+
+    for (i, c) in name.char_indices() {
+        if matches!(c, '-' | '_' | ' ') {
+            if let Some(start) = start.take() {
+                words.push(&name[start..i]);
+            }
+        } else {
+            match start {
+                Some(s)
+                    if prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit())
+                        && c.is_uppercase() =>
+                {
+                    words.push(&name[s..i]);
+                    start = Some(i);
+                }
+                Some(_) => {}
+                None => start = Some(i),
+            }
+        }
+        prev = Some(c);
+    }
+    if let Some(start) = start {
+        words.push(&name[start..]);
+    }
+
+    words
+}
@@ -1 +1,3 @@
+pub mod case;
 pub mod hierarchy;
+pub mod whitespace;
@@ -0,0 +1,156 @@
+use syn::ext::IdentExt;
+
+/// Options to clean up the whitespace of a message before it is parsed,
+/// written as a list such as `[dedent, trim]`.
+///
+/// The options are applied in the order `dedent`, `join_lines`, `trim`,
+/// regardless of the order they are written in.
+///
+/// # Example
+/// ```rust
+/// use local_fmt_macros_internal::utils::whitespace::Whitespace;
+///
+/// let whitespace = Whitespace {
+///     trim: true,
+///     dedent: true,
+///     join_lines: false,
+/// };
+/// assert_eq!(whitespace.apply("\n    Usage:\n      app run\n"), "Usage:\n  app run");
+///
+/// let whitespace = Whitespace {
+///     trim: true,
+///     dedent: false,
+///     join_lines: true,
+/// };
+/// assert_eq!(whitespace.apply("A long\n  message.\n\nNext.\n"), "A long message.\nNext.");
+/// assert_eq!(whitespace.apply("長い\nメッセージ。\nSee\nREADME.md"), "長いメッセージ。See README.md");
+///
+/// let whitespace = Whitespace {
+///     trim: false,
+///     dedent: true,
+///     join_lines: false,
+/// };
+/// assert_eq!(whitespace.apply("\r\n\u{3000}\u{3000}一\r\n\u{3000}\u{3000}\u{3000}二\r\n"), "\n一\n\u{3000}二\n");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Whitespace {
+    /// `trim`, removes the leading and trailing whitespace of the message.
+    pub trim: bool,
+    /// `dedent`, removes the indentation shared by all non-blank lines.
+    pub dedent: bool,
+    /// `join_lines`, joins consecutive lines with a single space,
+    /// or without a space next to a Chinese or Japanese character,
+    /// turning blank lines into line breaks.
+    pub join_lines: bool,
+}
+
+impl Whitespace {
+    pub const NAMES: &'static [&'static str] = &["trim", "dedent", "join_lines"];
+
+    /// Applies the options to the message.
+    pub fn apply(self, text: &str) -> String {
+        let mut text = text.to_string();
+        if self.dedent {
+            text = dedent(&text);
+        }
+        if self.join_lines {
+            text = join_lines(&text);
+        }
+        if self.trim {
+            text = text.trim().to_string();
+        }
+        text
+    }
+}
+
+impl syn::parse::Parse for Whitespace {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        syn::bracketed!(content in input);
+        let options = content.parse_terminated(syn::Ident::parse_any, syn::Token![,])?;
+
+        let mut whitespace = Self::default();
+        for option in options {
+            let slot = match option.to_string().as_str() {
+                "trim" => &mut whitespace.trim,
+                "dedent" => &mut whitespace.dedent,
+                "join_lines" => &mut whitespace.join_lines,
+                _ => {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        format!("expected one of {}", Self::NAMES.join(", ")),
+                    ))
+                }
+            };
+            *slot = true;
+        }
+        Ok(whitespace)
+    }
+}
+
+/// Removes the indentation shared by all non-blank lines, counted in characters.
+/// Lines are split like [`str::lines`], so `\r\n` line endings become `\n`.
+fn dedent(text: &str) -> String {
+    let indent = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().take_while(|c| c.is_whitespace()).count())
+        .min()
+        .unwrap_or(0);
+
+    let mut dedented = text
+        .lines()
+        .map(|line| match line.char_indices().nth(indent) {
+            Some((index, _)) if line[..index].trim().is_empty() => &line[index..],
+            _ => line.trim_start(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.ends_with('\n') {
+        dedented.push('\n');
+    }
+    dedented
+}
+
+fn join_lines(text: &str) -> String {
+    let mut paragraphs = Vec::<String>::new();
+    let mut current = String::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        // Words are not separated by spaces in Chinese and Japanese,
+        // so no space is inserted next to their characters.
+        let space = match (current.chars().next_back(), line.chars().next()) {
+            (Some(end), Some(start)) => !is_cjk(end) && !is_cjk(start),
+            _ => false,
+        };
+        if space {
+            current.push(' ');
+        }
+        current.push_str(line);
+    }
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+    paragraphs.join("\n")
+}
+
+/// Returns true for the characters of Chinese and Japanese, including their punctuation
+/// and the fullwidth forms.
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3000}'..='\u{30FF}'
+            | '\u{31F0}'..='\u{31FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FF00}'..='\u{FFEF}'
+            | '\u{20000}'..='\u{3FFFF}'
+    )
+}
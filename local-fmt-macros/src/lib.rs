@@ -7,29 +7,290 @@
 /// # Arguments
 ///
 /// * `name` - The name of the generated static message set.
+///   It is a `const` by default, and a lazily initialized `static` when written as `name = static NAME`,
+///   see [Field Types](#field-types).
 /// * `lang` - The enumeration representing the supported languages.
 /// * `message` - The struct containing the constant messages.
 /// * `supplier` - The language supplier, a function of type `fn() -> Lang`.
-/// It determines how the current language is selected dynamically at runtime.
+///   It determines how the current language is selected dynamically at runtime.
+/// * `file_type` (optional) - The format of the language definition files: `"toml"`, `"json"` or `"yaml"`.
+///   When omitted, the format of each file is inferred from its extension.
 /// * `def location` - Specifies the location of the language definition files. This can be either:
 ///     * `lang_file` - The path to a single language definition file.
 ///     * `lang_folder` - The folder containing multiple language definition files, one for each language.
+///     * `lang_layers` - A list of `lang_file` or `lang_folder` sources, see [Layers](#layers).
+/// * `layer_report` (optional) - The name of a generated `&[local_fmt::MessageLayer]` constant
+///   that tells which layer supplied each message.
+/// * `lang_names` (optional) - A table mapping variants of `lang` to the language names
+///   used in the files, such as `{ EnUs = "en-US", JA = "ja" }`.
+/// * `lang_rename` (optional) - A rename rule converting the other language names to variants,
+///   one of `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`, `"snake_case"`,
+///   `"SCREAMING_SNAKE_CASE"`, `"kebab-case"` or `"SCREAMING-KEBAB-CASE"`.
+///   For example, `"PascalCase"` loads `en-US.json` as `Lang::EnUs`.
+/// * `rename_all` (optional) - The naming convention of the keys in the files, one of the rules of `lang_rename`.
+///   For example, `"kebab-case"` loads the key `sign-in` into the field `sign_in`.
+/// * `rename` (optional) - A table mapping fields of `message` to the keys used in the files,
+///   such as `{ two_factor_prompt = "2fa-prompt", account.close = "delete-account" }`.
+///   Nested fields are written as a path, and the key is the one at the level of the field.
+/// * `whitespace` (optional) - Whitespace options applied to every message, see [Whitespace](#whitespace).
+/// * `key_whitespace` (optional) - A table of whitespace options for the messages at or under a field,
+///   such as `{ help.usage = [dedent, trim] }`.
+/// * `accessors` (optional) - A variant of `lang` used as the source language of the generated
+///   accessor methods, see [Descriptions and Accessors](#descriptions-and-accessors).
+/// * `allow_unused_args` (optional) - `true`, or a list of fields such as `[inbox, account.deleted]`,
+///   whose messages may leave some arguments unused, see [Unused Arguments](#unused-arguments).
+///
+/// The arguments after `supplier` can be given in any order.
 ///
 /// # Notes
-/// * The language definition file(s) must be in the TOML format.
+/// * The format of a language definition file is inferred from its extension:
+///   `.toml`, `.json`, `.jsonc` (JSON with comments), `.yaml` or `.yml`,
+///   and the matching `macros-*` feature must be enabled. Only `.jsonc` files can contain comments.
+///   A `lang_folder` can mix several formats. When `file_type` is given,
+///   only the files of that format are loaded.
+/// * Files of a `lang_folder` with another extension are ignored, and are listed in a warning,
+///   reported as the use of a deprecated item, which `#[allow(deprecated)]` silences.
 /// * The `def location` expands to `CARGO_MANIFEST_DIR/{your_path}`, where `CARGO_MANIFEST_DIR`
 ///   is an environment variable representing the directory containing the Cargo.toml file of your project.
 ///   This ensures that paths are resolved relative to the project's root directory.
+/// * Every file read by the macro is registered as a build dependency,
+///   so editing a language file triggers a recompilation.
+///   A procedural macro cannot watch a folder, so adding a file to a `lang_folder` does not
+///   trigger a recompilation by itself. Call `local_fmt::build::track_lang_folder("langs")`
+///   from your `build.rs` to pick up new files as well.
+/// * Literal braces in messages are written as `{{` and `}}`, like in [`gen_static_message!`].
 ///
 /// ## Message Nesting
 /// * The `message` struct can be nested, allowing for organized grouping of related messages.
 ///   For example, you can have a struct for action messages nested within a main message struct.
 ///   This helps in maintaining a clean and structured message hierarchy.
 ///
+/// ## Key Names
+/// * By default, every key in the files must be the name of a field of `message`.
+///   With `rename_all`, every key must follow the given convention instead,
+///   and a key such as `signIn` in a `"kebab-case"` file is reported as an error.
+///   Keys that are not valid field names, such as `2fa-prompt`, are mapped with `rename`,
+///   which takes precedence over `rename_all`.
+///
+/// ## Whitespace
+/// * Multiline strings of TOML or YAML keep their indentation and trailing newlines.
+///   The whitespace options clean up a message before it is parsed:
+///     * `trim` - Removes the leading and trailing whitespace.
+///     * `dedent` - Removes the indentation shared by all non-blank lines.
+///     * `join_lines` - Joins consecutive lines with a single space, turning blank lines into line breaks.
+///       No space is inserted next to a Chinese or Japanese character, as their words are not separated by spaces.
+/// * The options are applied in the order `dedent`, `join_lines`, `trim`.
+///   The options of `key_whitespace` replace those of `whitespace`, and the longest matching field wins:
+///
+/// ```text
+/// whitespace = [trim, join_lines],
+/// key_whitespace = { help = [dedent, trim] },
+/// ```
+///
+/// ## Descriptions and Accessors
+/// * The keys `_description`, `_args` and `_terms` are reserved, while other keys starting with `_`,
+///   such as `_legacy`, are messages like any other key. The `_description` table describes the messages
+///   next to it for translators, and is not part of the messages:
+///
+/// ```toml
+/// hello = "Hello, {0}!"
+///
+/// [_description]
+/// hello = "Greeting on the home page. {0} is the name of the user."
+/// ```
+///
+/// * With `accessors = EN`, a method is generated for each message of the `message` structs,
+///   such as `MESSAGES.hello(user)`. Its documentation shows the description and the text in `EN`.
+///   Messages with arguments take one `impl Display` parameter per argument and return the formatted `String`,
+///   see [Placeholder Kinds](#placeholder-kinds), and the others return an `impl Display` of their text.
+///   Numbers and booleans get no accessor, as the type of their field is not known to the macro.
+/// * The parameters are named after the `_args` table of `EN`, which lists the names of each message
+///   separated by commas, or after named placeholders such as `{0=user}`,
+///   and are named `arg0`, `arg1`, ... otherwise:
+///
+/// ```toml
+/// hello = "Hello, {0=user}!"
+/// usage = "Usage: app {0} {1}"
+///
+/// [_args]
+/// usage = "command, options"
+/// ```
+///
+/// ## Placeholder Kinds
+/// * A placeholder can declare the kind of its argument, written as `{0:number}`, `{1:date}`, `{2:text}`
+///   or `{3:msg}`, or `{0=count:number}` along with a name.
+///   Names and kinds can only be declared in the files of `def_local_fmt`,
+///   `gen_static_message!` and `gen_alloc_message!` reject them.
+///   The kinds are declared in the language of `accessors`. The other languages may repeat a kind,
+///   but declaring another kind, or a kind that the language of `accessors` does not declare,
+///   is a compile error.
+/// * A kind belongs to the argument, not to one language: a placeholder without a kind,
+///   such as `{1}` in `JA` next to `{1:number}` in `EN`, inherits the kind declared by the other languages.
+/// * The kinds are only enforced by the accessors. `format(&[&str])` and `local_format!`
+///   still take any argument. Without `accessors`, the kinds are advisory: they document the arguments
+///   for translators, and are only checked not to conflict between the languages.
+/// * The accessors take an `impl local_fmt::NumberArg` for a number, implemented for the primitive
+///   integers and floats, an `impl local_fmt::DateArg` for a date, implemented by the date types
+///   of the application, and an `impl Display` for a text or an argument without a kind.
+/// * The accessors take an `impl local_fmt::MessageArg` for a message, which is another message
+///   with its own arguments created by `local_format_args!`, such as
+///   `MESSAGES.distance(local_format_args!(MESSAGES.units.kilometers, 42))`,
+///   or `local_format_args!(MESSAGES.units.unknown)` for a message without arguments.
+///   Both messages check their number of arguments, and the inner message is written
+///   directly into the result without an intermediate `String`.
+///
+/// ```toml
+/// [EN]
+/// inbox = "{0=user:text}, you have {1:number} new messages since {2:date}."
+/// distance = "The distance is {0:msg}."
+///
+/// [EN.units]
+/// kilometers = "{0:number} km"
+///
+/// [JA]
+/// inbox = "{0}さん、{2}以降に{1}件の新着メッセージがあります。"
+/// distance = "距離は{0}です。"
+///
+/// [JA.units]
+/// kilometers = "{0}キロ"
+/// ```
+///
+/// ## Defaults and Conditional Sections
+/// * A placeholder written as `{1|anonymous}` is rendered as its default text when the argument is empty.
+///   The default text ends at the first `}`, and can contain `\{`, `\}` and `\\`.
+/// * A conditional section written as `{1? ({1})}` is rendered only when the argument is not empty,
+///   and ends at the first `}` that does not close a placeholder, so a literal `}` in a section
+///   is written `\}`. Sections can be nested.
+/// * Both count as a use of the argument, like `{1}`.
+///
+/// ```toml
+/// [EN]
+/// signed_in = "Signed in as {0|a guest}{1? ({1})}"
+/// ```
+///
+/// ## Layers
+/// * `lang_layers` loads several sources in order, and each layer overrides the messages
+///   of the earlier layers key by key. A layer only needs to contain the keys it overrides.
+///   Each layer is written in parentheses and can set its own `file_type`,
+///   falling back to the `file_type` of the invocation or to the extension of each file:
+///
+/// ```text
+/// lang_layers = [
+///     (lang_folder = "langs"),
+///     (file_type = "json", lang_file = "customers/acme.txt"),
+/// ],
+/// layer_report = MESSAGES_LAYERS,
+/// ```
+///
+/// ## Key References
+/// * A message can include another message of the same language with `{@key}`,
+///   where `key` is the path of fields such as `{@words.product}`.
+///   The referenced message is inlined at compile time, after all layers are applied,
+///   and its placeholders become placeholders of the referencing message, with their names and kinds,
+///   such as `{0=count:number}`. A placeholder named or declared differently by the two messages is an error:
+///
+/// ```toml
+/// greeting = "Hello, {0}."
+/// signed_in = "{@greeting} You have {1} new messages." # StaticMessage<2>
+///
+/// [words]
+/// product = "Rustacean Hub"
+/// ```
+///
+/// * References that form a cycle, or that do not refer to a message, are reported as errors.
+///
+/// ## Terms
+/// * The reserved `_terms` table at the root of a language holds terms such as product names,
+///   which are used in its messages as `{$term}` and are not part of the messages.
+///   In a `lang_folder`, the terms can also be written in `EN/_terms.toml`,
+///   but not in a namespace folder such as `EN/errors/_terms.toml`.
+///   Using a term that is not defined, or defining a term that is never used, is reported as an error.
+///   With `lang_layers`, a term counts as used when a message of any layer uses it,
+///   even if that message is overridden by a later layer:
+///
+/// ```toml
+/// welcome = "Welcome to {$product}, {0}!"
+///
+/// [_terms]
+/// product = "Rustacean Hub"
+/// ```
+///
 /// ## Static String Loading
 /// * If a message does not require any arguments, it can be loaded as a `&'static str`.
 ///   This allows for efficient handling of static messages without the need for formatting.
 ///   Simply define the message field as `&'static str` in your message struct.
+/// * Such messages can also use constants in scope of the macro call,
+///   written as `{u:MAX_MEMBERS}`, `{f.1:RATIO}` or `{PRODUCT}` like in [`gen_static_message!`],
+///   and are folded into a `&'static str` at compile time:
+///
+/// ```toml
+/// limit = "You can invite up to {u:MAX_MEMBERS} members."
+/// ```
+///
+/// ## Field Types
+/// * A message can be loaded into a field of type `&'static str`, `Cow<'static, str>`
+///   or `StaticMessage<N>`, where `N` is the number of arguments of the message.
+///   A message without arguments can be loaded into a `StaticMessage<0>` as well as a `&'static str`.
+/// * `String` and `AllocMessage<N>` fields cannot be created in a constant.
+///   Writing `name = static MESSAGES` defines a `static` `LazyLock<LocalFmt<..>>` instead,
+///   whose fields are created when it is first used.
+///   The number of arguments of each message is still checked when the crate is built.
+/// * The accessors of messages without arguments return an `impl Display`,
+///   whatever the type of their field, such as `MESSAGES.title().to_string()`.
+///
+/// ```rust
+/// # #![cfg(feature = "toml")]
+///
+/// use enum_table::Enumable;
+/// use local_fmt::{def_local_fmt, AllocMessage};
+///
+/// #[derive(Clone, Copy, Enumable)]
+/// enum Lang {
+///     EN,
+///     JA,
+/// }
+///
+/// struct Messages {
+///     pub hello: AllocMessage<1>,
+/// }
+///
+/// def_local_fmt!(
+///     name = static MESSAGES,
+///     lang = Lang,
+///     message = Messages,
+///     supplier = || Lang::EN,
+///     file_type = "toml",
+///     lang_folder = "doctest/langs"
+/// );
+///
+/// assert_eq!(MESSAGES.hello.format(&["Rust"]), "Hello, world! Rust");
+/// ```
+///
+/// ## Unused Arguments
+/// * By default, the number of arguments of a message is one more than its highest placeholder,
+///   and every placeholder below it must be used, so that a message never drops an argument by mistake.
+/// * Some languages naturally leave an argument out, such as a pronoun or a count.
+///   For the messages at or under the fields listed in `allow_unused_args`, or every message with `true`,
+///   the `StaticMessage<N>` or `AllocMessage<N>` field defines the number of arguments instead,
+///   and a language may use any of them, while using a placeholder of `N` or above is still a compile error.
+/// * The accessors take the arguments of the source language, which should use all of them.
+///
+/// ```toml
+/// [EN]
+/// inbox = "{0}, you have {1} new messages."
+///
+/// [JA]
+/// inbox = "新着メッセージが{1}件あります。"
+/// ```
+///
+/// ## Numbers and Booleans
+/// * Numbers and booleans in the files can be loaded into fields of type `bool`, `u8`..`u128`,
+///   `usize`, `i8`..`i128`, `isize`, `f32` and `f64`, so that per-language settings
+///   such as the first weekday can live alongside the messages.
+///   A value that does not fit the type of its field is a compile error.
+/// * They can also be loaded into `&'static str` fields, where they are rendered as text.
+///   A float is rendered from its value, as the shortest text that reads back as the same number,
+///   with a fraction or an exponent, so `2.0` is rendered as `2.0` and `1.10` as `1.1`.
 ///
 /// # Example
 ///
@@ -74,6 +335,24 @@
 /// hello = "こんにちは、世界！{0}"
 /// ```
 ///
+/// ## Example with namespaces in `lang_folder = "langs"`
+///
+/// A language can also be a folder. Each file or subfolder inside it becomes
+/// a nested message named after its file stem, and subfolders can be nested further:
+///
+/// <pre>
+/// /langs
+/// ├── EN
+/// │   ├── common.toml   # common.hello
+/// │   ├── errors.toml   # errors.not_found
+/// │   └── errors
+/// │       └── http.toml # errors.http.timeout
+/// └── JA.toml
+/// </pre>
+///
+/// Files and folders of the same name (`EN.toml` and `EN/`, `errors.toml` and `errors/`)
+/// are merged, and a key defined twice is reported as an error.
+///
 ///
 /// # Example 1
 ///
@@ -177,6 +456,13 @@ pub fn def_local_fmt(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// - The macro supports using constants within the message string.
 /// - You can include numeric constants directly in the message using the `{u:}` or `{i:}` syntax
 ///   for unsigned and signed integers, respectively.
+/// - Float, char and bool constants are included with the `{f:}`, `{c:}` and `{b:}` syntax.
+///   Floats are rendered like `{}` of `Display`, and `{f.2:}` renders two digits after the point
+///   like `{:.2}`, with a precision from 0 to 255.
+/// - A literal `{` or `}` is written as `{{` or `}}`, and a literal backslash as `\\`.
+///   `\{` and `\}` are also a literal `{` and `}`, and a `}` that does not close
+///   a placeholder is kept as it is. This is the syntax of `AllocMessage::from_str`,
+///   which parses the `Display` of a message back to the same message.
 ///
 /// # Examples
 ///
@@ -220,6 +506,18 @@ pub fn def_local_fmt(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 ///     let text = MESSAGE.format(&["World!"]);
 ///     assert_eq!(text, "Hello! World! -123456789");
 /// }
+///
+/// // Example with float, char and bool
+/// {
+///     const VERSION: f32 = 1.1;
+///     const RATIO: f64 = 2.0 / 3.0;
+///     const ARROW: char = '→';
+///     const ENABLED: bool = true;
+///     const MESSAGE: StaticMessage<1> =
+///         gen_static_message!("{0} {f:VERSION} {c:ARROW} {f.2:RATIO} {b:ENABLED}");
+///     let text = MESSAGE.format(&["Version"]);
+///     assert_eq!(text, "Version 1.1 → 0.67 true");
+/// }
 #[proc_macro]
 pub fn gen_static_message(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let args = syn::parse_macro_input!(input as local_fmt_macros_internal::util_macro::Args);
@@ -240,6 +538,7 @@ pub fn gen_static_message(input: proc_macro::TokenStream) -> proc_macro::TokenSt
 /// - The number of placeholders in the message must match the number of arguments
 ///   specified in the `AllocMessage` type.
 /// - The macro supports using ident within the message string.
+/// - Braces and backslashes are escaped like in [`gen_static_message!`].
 ///
 /// # Examples
 ///
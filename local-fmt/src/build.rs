@@ -0,0 +1,20 @@
+//! Helpers for the `build.rs` of a crate that uses `def_local_fmt!`.
+
+/// Makes cargo rebuild the crate when a file is added to or removed from a `lang_folder`.
+///
+/// `def_local_fmt!` registers every file it reads as a build dependency,
+/// but a procedural macro cannot watch a folder, so a file newly added to a `lang_folder`
+/// is not picked up until the crate is rebuilt for another reason.
+/// Calling this function from `build.rs` closes that gap, as cargo then reruns the build script,
+/// and rebuilds the crate, whenever anything in the folder changes.
+///
+/// The path is relative to the `Cargo.toml` of the crate, like `lang_folder`.
+///
+/// # Example
+/// ```no_run
+/// // In `fn main` of build.rs, with local-fmt in [build-dependencies]:
+/// local_fmt::build::track_lang_folder("langs");
+/// ```
+pub fn track_lang_folder(path: &str) {
+    println!("cargo:rerun-if-changed={}", path);
+}
@@ -0,0 +1,28 @@
+/// Describes which source supplied a message when `def_local_fmt` loads several `lang_layers`.
+///
+/// The `def_local_fmt` macro generates a `&[MessageLayer]` constant, sorted by language and key,
+/// when the `layer_report` argument is given.
+///
+/// # Example
+/// ```
+/// use local_fmt::MessageLayer;
+///
+/// const REPORT: &[MessageLayer] = &[
+///     MessageLayer { lang: "EN", key: "hello", layer: 0, source: "langs" },
+///     MessageLayer { lang: "EN", key: "words.brand", layer: 1, source: "customer/EN.toml" },
+/// ];
+///
+/// let overridden = REPORT.iter().filter(|m| m.layer > 0).map(|m| m.key).collect::<Vec<_>>();
+/// assert_eq!(overridden, ["words.brand"]);
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MessageLayer {
+    /// The language of the message, as written in the language files.
+    pub lang: &'static str,
+    /// The key of the message, with nested keys joined by a period.
+    pub key: &'static str,
+    /// The index of the layer in `lang_layers`.
+    pub layer: usize,
+    /// The `lang_file` or `lang_folder` of the layer.
+    pub source: &'static str,
+}
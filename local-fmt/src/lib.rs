@@ -12,6 +12,14 @@ pub use utils::*;
 pub mod message;
 pub use message::*;
 
+pub mod layer;
+pub use layer::*;
+
+pub mod local_format;
+pub use local_format::*;
+
+pub mod build;
+
 #[cfg(feature = "serde")]
 mod serde;
 
@@ -29,26 +37,26 @@ pub struct LocalFmt<L: Enumable + Copy, M, const N: usize> {
 
 impl<L: Enumable + Copy, M, const N: usize> LocalFmt<L, M, N> {
     /// Creates a new LocalFmt instance with the given messages and language supplier function.
-    /// 
+    ///
     /// # Arguments
     /// * `messages` - An EnumTable containing messages for each language variant
     /// * `lang` - A function that returns the current language when called
-    /// 
+    ///
     /// # Example
     /// ```
     /// use local_fmt::LocalFmt;
     /// use enum_table::{EnumTable, et};
-    /// 
+    ///
     /// #[derive(Clone, Copy, enum_table::Enumable)]
     /// enum Lang { EN, JA }
-    /// 
+    ///
     /// struct Messages { hello: &'static str }
-    /// 
+    ///
     /// let messages = et!(Lang, Messages, |lang| match lang {
     ///     Lang::EN => Messages { hello: "Hello" },
     ///     Lang::JA => Messages { hello: "こんにちは" },
     /// });
-    /// 
+    ///
     /// let fmt = LocalFmt::new(messages, || Lang::EN);
     /// assert_eq!(fmt.get_message().hello, "Hello");
     /// ```
@@ -57,32 +65,32 @@ impl<L: Enumable + Copy, M, const N: usize> LocalFmt<L, M, N> {
     }
 
     /// Returns the message in the current language.
-    /// 
+    ///
     /// This method calls the language supplier function to determine the current language,
     /// then returns the corresponding message from the internal EnumTable.
-    /// 
+    ///
     /// # Example
     /// ```
     /// use local_fmt::LocalFmt;
     /// use enum_table::{EnumTable, et};
     /// use std::sync::RwLock;
-    /// 
+    ///
     /// #[derive(Clone, Copy, enum_table::Enumable)]
     /// enum Lang { EN, JA }
-    /// 
+    ///
     /// struct Messages { greeting: &'static str }
-    /// 
+    ///
     /// static CURRENT_LANG: RwLock<Lang> = RwLock::new(Lang::EN);
-    /// 
+    ///
     /// let messages = et!(Lang, Messages, |lang| match lang {
     ///     Lang::EN => Messages { greeting: "Hello" },
     ///     Lang::JA => Messages { greeting: "こんにちは" },
     /// });
-    /// 
+    ///
     /// let fmt = LocalFmt::new(messages, || *CURRENT_LANG.read().unwrap());
-    /// 
+    ///
     /// assert_eq!(fmt.get_message().greeting, "Hello");
-    /// 
+    ///
     /// *CURRENT_LANG.write().unwrap() = Lang::JA;
     /// assert_eq!(fmt.get_message().greeting, "こんにちは");
     /// ```
@@ -91,22 +99,22 @@ impl<L: Enumable + Copy, M, const N: usize> LocalFmt<L, M, N> {
     }
 
     /// Returns the current language by calling the language supplier function.
-    /// 
+    ///
     /// # Example
     /// ```
     /// use local_fmt::LocalFmt;
     /// use enum_table::{EnumTable, et};
-    /// 
+    ///
     /// #[derive(Clone, Copy, Debug, PartialEq, enum_table::Enumable)]
     /// enum Lang { EN, JA }
-    /// 
+    ///
     /// struct Messages { hello: &'static str }
-    /// 
+    ///
     /// let messages = et!(Lang, Messages, |lang| match lang {
     ///     Lang::EN => Messages { hello: "Hello" },
     ///     Lang::JA => Messages { hello: "こんにちは" },
     /// });
-    /// 
+    ///
     /// let fmt = LocalFmt::new(messages, || Lang::EN);
     /// assert_eq!(fmt.lang(), Lang::EN);
     /// ```
@@ -0,0 +1,211 @@
+use std::fmt::{Display, Write};
+
+use crate::{message::skip_section, Message, RefMessageFormat};
+
+/// A message and its arguments, which are formatted when displayed.
+///
+/// This is created by [`local_format_args!`](crate::local_format_args), and is what
+/// [`local_format!`](crate::local_format) and [`local_write!`](crate::local_write) format.
+/// Unlike [`Message::format`], the arguments can be of any type implementing [`Display`],
+/// and no intermediate `String` is allocated.
+///
+/// The message is any [`Message`], such as a [`StaticMessage`](crate::StaticMessage)
+/// or an [`AllocMessage`](crate::AllocMessage).
+/// As it implements [`Display`], a formatted message can itself be the argument of another message,
+/// such as an argument declared as `{0:msg}`, and is written directly into the outer message.
+///
+/// # Example
+/// ```
+/// use local_fmt::{gen_static_message, local_format, local_format_args, StaticMessage};
+///
+/// const MESSAGE: StaticMessage<2> = gen_static_message!("{0} has {1} new messages");
+///
+/// let args = local_format_args!(MESSAGE, "Rust", 3);
+/// assert_eq!(args.to_string(), "Rust has 3 new messages");
+///
+/// const UNIT: StaticMessage<1> = gen_static_message!("{0} km");
+/// const DISTANCE: StaticMessage<1> = gen_static_message!("The distance is {0}.");
+///
+/// assert_eq!(
+///     local_format!(DISTANCE, local_format_args!(UNIT, 42)),
+///     "The distance is 42 km."
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct LocalFormatArgs<'a, const N: usize> {
+    message: &'a dyn Message<N>,
+    args: [&'a dyn Display; N],
+}
+
+impl<'a, const N: usize> LocalFormatArgs<'a, N> {
+    /// Creates the arguments of a message from a tuple of references to the arguments,
+    /// failing to compile if the number of arguments does not match the message.
+    pub fn new(message: &'a impl Message<N>, args: impl LocalArgs<'a, N>) -> Self {
+        Self {
+            message,
+            args: args.into_args(),
+        }
+    }
+}
+
+impl<const N: usize> Display for LocalFormatArgs<'_, N> {
+    /// Writes the message with its arguments.
+    ///
+    /// The arguments of defaults such as `{0|none}` and of conditional sections such as `{0? ...}`
+    /// are first rendered into a `String` to know whether they are empty, and every placeholder
+    /// of these arguments writes that text, so that each argument is formatted only once.
+    /// The other arguments are written directly.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut rendered: [Option<String>; N] = [const { None }; N];
+        for segment in self.message.segments() {
+            if let RefMessageFormat::PlaceholderDefault(n, _) | RefMessageFormat::SectionStart(n) =
+                segment
+            {
+                if rendered[n].is_none() {
+                    let mut text = String::new();
+                    write!(text, "{}", self.args[n])?;
+                    rendered[n] = Some(text);
+                }
+            }
+        }
+        let text = |n: usize| rendered[n].as_deref().unwrap_or_default();
+
+        let mut segments = self.message.segments();
+        while let Some(segment) = segments.next() {
+            match segment {
+                RefMessageFormat::RefText(text) => f.write_str(text)?,
+                RefMessageFormat::Char(c) => c.fmt(f)?,
+                RefMessageFormat::Placeholder(n) => match &rendered[n] {
+                    Some(text) => f.write_str(text)?,
+                    None => self.args[n].fmt(f)?,
+                },
+                RefMessageFormat::PlaceholderDefault(n, default) if text(n).is_empty() => {
+                    f.write_str(default)?
+                }
+                RefMessageFormat::PlaceholderDefault(n, _) => f.write_str(text(n))?,
+                RefMessageFormat::SectionStart(n) if text(n).is_empty() => {
+                    skip_section(&mut segments)
+                }
+                RefMessageFormat::SectionStart(_) | RefMessageFormat::SectionEnd => {}
+                segment => segment.fmt(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The arguments of a message with `N` arguments,
+/// implemented for tuples of `N` references to values implementing [`Display`],
+/// for messages of up to 12 arguments.
+#[diagnostic::on_unimplemented(
+    message = "the message takes {N} arguments, but the arguments given are `{Self}`",
+    label = "expected {N} arguments",
+    note = "every argument must also implement `Display`",
+    note = "messages of more than 12 arguments cannot be used with `local_format!`, use `Message::format` instead"
+)]
+pub trait LocalArgs<'a, const N: usize> {
+    fn into_args(self) -> [&'a dyn Display; N];
+}
+
+macro_rules! impl_local_args {
+    ($n:literal; $($ty:ident $arg:ident),*) => {
+        impl<'a, $($ty: Display),*> LocalArgs<'a, $n> for ($(&'a $ty,)*) {
+            fn into_args(self) -> [&'a dyn Display; $n] {
+                let ($($arg,)*) = self;
+                [$($arg as &'a dyn Display),*]
+            }
+        }
+    };
+}
+
+impl_local_args!(0;);
+impl_local_args!(1; A a);
+impl_local_args!(2; A a, B b);
+impl_local_args!(3; A a, B b, C c);
+impl_local_args!(4; A a, B b, C c, D d);
+impl_local_args!(5; A a, B b, C c, D d, E e);
+impl_local_args!(6; A a, B b, C c, D d, E e, F f);
+impl_local_args!(7; A a, B b, C c, D d, E e, F f, G g);
+impl_local_args!(8; A a, B b, C c, D d, E e, F f, G g, H h);
+impl_local_args!(9; A a, B b, C c, D d, E e, F f, G g, H h, I i);
+impl_local_args!(10; A a, B b, C c, D d, E e, F f, G g, H h, I i, J j);
+impl_local_args!(11; A a, B b, C c, D d, E e, F f, G g, H h, I i, J j, K k);
+impl_local_args!(12; A a, B b, C c, D d, E e, F f, G g, H h, I i, J j, K k, L l);
+
+/// Creates a [`LocalFormatArgs`] from a message and its arguments, like [`format_args!`].
+///
+/// The message is any expression implementing [`Message<N>`](crate::Message), such as `MESSAGES.hello`,
+/// and the arguments are expressions implementing [`Display`].
+/// Giving a number of arguments other than `N` fails to compile.
+///
+/// Messages of up to 12 arguments are supported, as [`LocalArgs`] is implemented for tuples
+/// of up to 12 elements. A message with more arguments is formatted with
+/// [`Message::format`](crate::Message::format) instead.
+///
+/// The result borrows the message and the arguments, so it is meant to be used directly,
+/// such as in `panic!("{}", local_format_args!(MESSAGES.hello, user))`.
+///
+/// # Example
+/// ```
+/// use local_fmt::{gen_static_message, local_format_args, StaticMessage};
+///
+/// const MESSAGE: StaticMessage<1> = gen_static_message!("Hello, {0}!");
+///
+/// assert_eq!(format!("{}", local_format_args!(MESSAGE, "Rust")), "Hello, Rust!");
+/// ```
+///
+/// ```compile_fail
+/// use local_fmt::{gen_static_message, local_format_args, StaticMessage};
+///
+/// const MESSAGE: StaticMessage<1> = gen_static_message!("Hello, {0}!");
+///
+/// local_format_args!(MESSAGE, "Rust", "World");
+/// ```
+#[macro_export]
+macro_rules! local_format_args {
+    ($message:expr $(, $arg:expr)* $(,)?) => {
+        $crate::LocalFormatArgs::new(&$message, ($(&$arg,)*))
+    };
+}
+
+/// Formats a message with its arguments into a `String`, like [`format!`].
+///
+/// See [`local_format_args!`](crate::local_format_args) for the arguments.
+///
+/// # Example
+/// ```
+/// use local_fmt::{gen_static_message, local_format, StaticMessage};
+///
+/// const MESSAGE: StaticMessage<2> = gen_static_message!("{0} + {1}");
+///
+/// assert_eq!(local_format!(MESSAGE, 1, 2.5), "1 + 2.5");
+/// ```
+#[macro_export]
+macro_rules! local_format {
+    ($($arg:tt)*) => {
+        ::std::string::ToString::to_string(&$crate::local_format_args!($($arg)*))
+    };
+}
+
+/// Writes a message with its arguments into a writer, like [`write!`].
+///
+/// The writer is anything [`write!`] accepts, such as a `std::fmt::Formatter` or a `String`.
+/// See [`local_format_args!`](crate::local_format_args) for the other arguments.
+///
+/// # Example
+/// ```
+/// use std::fmt::Write;
+/// use local_fmt::{gen_static_message, local_write, StaticMessage};
+///
+/// const MESSAGE: StaticMessage<1> = gen_static_message!("Hello, {0}!");
+///
+/// let mut text = String::new();
+/// local_write!(text, MESSAGE, "Rust").unwrap();
+/// assert_eq!(text, "Hello, Rust!");
+/// ```
+#[macro_export]
+macro_rules! local_write {
+    ($dst:expr, $($arg:tt)*) => {
+        ::std::write!($dst, "{}", $crate::local_format_args!($($arg)*))
+    };
+}
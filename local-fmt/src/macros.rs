@@ -1,39 +1,307 @@
+use std::borrow::Cow;
+
 use local_fmt_macros::gen_static_message;
 use sealed::Sealed;
 
-use crate::{panic_builder, StaticMessage};
+use crate::{panic_builder, AllocMessage, RefMessage, RefMessageFormat, StaticMessage};
 
 mod sealed {
-    use crate::StaticMessage;
+    use crate::{RefMessageFormat, StaticMessage};
+
+    use super::StaticText;
+
+    pub trait Sealed {
+        /// True for a [`StaticText`], false for a [`StaticMessage`].
+        const IS_TEXT: bool;
+
+        fn text(&self) -> &'static str;
+
+        fn formats(&self) -> &'static [RefMessageFormat<'static>];
+    }
+
+    impl Sealed for StaticText {
+        const IS_TEXT: bool = true;
+
+        fn text(&self) -> &'static str {
+            self.text
+        }
+
+        fn formats(&self) -> &'static [RefMessageFormat<'static>] {
+            self.message.formats()
+        }
+    }
+
+    impl<T: Sealed> Sealed for super::UnusedArgs<T> {
+        const IS_TEXT: bool = T::IS_TEXT;
+
+        fn text(&self) -> &'static str {
+            self.0.text()
+        }
+
+        fn formats(&self) -> &'static [RefMessageFormat<'static>] {
+            self.0.formats()
+        }
+    }
+
+    impl<const N: usize> Sealed for StaticMessage<N> {
+        const IS_TEXT: bool = false;
+
+        fn text(&self) -> &'static str {
+            ""
+        }
+
+        fn formats(&self) -> &'static [RefMessageFormat<'static>] {
+            StaticMessage::formats(self)
+        }
+    }
+}
+
+/// A message without arguments, emitted by the `def_local_fmt` macro both as a text
+/// and as a message, so that it can be loaded into a `&'static str` or a `StaticMessage<0>` field.
+#[derive(Debug, Clone)]
+pub struct StaticText {
+    /// The message folded into a text, with its constants such as `{u:MAX}` formatted.
+    pub text: &'static str,
+    pub message: StaticMessage<0>,
+}
+
+/// A message of a key whose languages may leave some arguments unused,
+/// emitted by the `def_local_fmt` macro for the keys of `allow_unused_args`.
+///
+/// A `StaticMessage<M>` wrapped in `UnusedArgs` can be loaded into a field
+/// with `N` arguments as long as `M <= N`. The field is then a message that leaves
+/// some arguments unused, as created by [`RefMessage::new_with_unused_args`]
+/// and [`AllocMessage::new_with_unused_args`].
+#[repr(transparent)]
+#[derive(Debug, Clone)]
+pub struct UnusedArgs<T>(pub T);
+
+/// The kind of a field type that a message can be loaded into.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    Str,
+    Cow,
+    String,
+    Static,
+    Alloc,
+}
+
+/// Trait for the field types that a message of a language file can be loaded into:
+/// `&'static str`, `Cow<'static, str>`, `String`, `StaticMessage<N>` and `AllocMessage<N>`.
+///
+/// `String` and `AllocMessage<N>` fields require the `def_local_fmt` macro
+/// to define a `static`, as they are allocated when the messages are first used.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait FromStaticMessage: field_sealed::Sealed + Sized {
+    #[doc(hidden)]
+    const KIND: MessageKind;
+    /// The name of the type, used in the error messages.
+    const NAME: &'static str;
+
+    /// Creates the field from a message whose number of arguments is checked
+    /// by [`CheckStaticMessageArg::IS_INVALID`].
+    ///
+    /// # Safety
+    /// The message must not use more arguments than the field has.
+    #[doc(hidden)]
+    unsafe fn from_message(
+        text: &'static str,
+        formats: &'static [RefMessageFormat<'static>],
+    ) -> Self;
+}
 
+mod field_sealed {
     pub trait Sealed {}
+}
+
+impl field_sealed::Sealed for &'static str {}
+impl FromStaticMessage for &'static str {
+    const KIND: MessageKind = MessageKind::Str;
+    const NAME: &'static str = "&'static str";
+
+    unsafe fn from_message(text: &'static str, _: &'static [RefMessageFormat<'static>]) -> Self {
+        text
+    }
+}
+
+impl field_sealed::Sealed for Cow<'static, str> {}
+impl FromStaticMessage for Cow<'static, str> {
+    const KIND: MessageKind = MessageKind::Cow;
+    const NAME: &'static str = "Cow<'static, str>";
 
-    impl Sealed for &'static str {}
-    impl<const N: usize> Sealed for StaticMessage<N> {}
+    unsafe fn from_message(text: &'static str, _: &'static [RefMessageFormat<'static>]) -> Self {
+        Cow::Borrowed(text)
+    }
+}
+
+impl field_sealed::Sealed for String {}
+impl FromStaticMessage for String {
+    const KIND: MessageKind = MessageKind::String;
+    const NAME: &'static str = "String";
+
+    unsafe fn from_message(text: &'static str, _: &'static [RefMessageFormat<'static>]) -> Self {
+        text.to_string()
+    }
+}
+
+impl<const N: usize> field_sealed::Sealed for StaticMessage<N> {}
+impl<const N: usize> FromStaticMessage for StaticMessage<N> {
+    const KIND: MessageKind = MessageKind::Static;
+    const NAME: &'static str = "StaticMessage<N>";
+
+    unsafe fn from_message(_: &'static str, formats: &'static [RefMessageFormat<'static>]) -> Self {
+        match StaticMessage::new_with_unused_args(formats) {
+            Ok(message) => message,
+            Err(error) => error.panic(),
+        }
+    }
+}
+
+impl<const N: usize> field_sealed::Sealed for AllocMessage<N> {}
+impl<const N: usize> FromStaticMessage for AllocMessage<N> {
+    const KIND: MessageKind = MessageKind::Alloc;
+    const NAME: &'static str = "AllocMessage<N>";
+
+    unsafe fn from_message(_: &'static str, formats: &'static [RefMessageFormat<'static>]) -> Self {
+        match RefMessage::<N>::new_with_unused_args(formats) {
+            Ok(message) => message.to_alloc(),
+            Err(error) => error.panic(),
+        }
+    }
 }
 
 /// Trait for validating static message arguments at compile time.
-/// 
+///
 /// This trait is used internally by the `def_local_fmt` macro to ensure that
 /// message arguments have compatible types. It provides compile-time validation
 /// that prevents type mismatches when defining localized messages.
-/// 
+///
 /// # Implementation Details
-/// 
+///
 /// The trait uses an associated constant `IS_INVALID` to signal validation errors.
 /// When `IS_INVALID` is `Some`, it contains an error message that will be displayed
 /// at compile time. When it's `None`, the type conversion is valid.
-/// 
+///
+/// A [`StaticText`] can be loaded into any field without arguments,
+/// including a `StaticMessage<0>` or an `AllocMessage<0>`,
+/// and a `StaticMessage<M>` into a `StaticMessage<M>` or an `AllocMessage<M>`.
+///
 /// This trait is sealed and cannot be implemented outside of this crate.
 pub trait CheckStaticMessageArg<To>: Sealed {
     /// Contains an error message if the type conversion is invalid, or None if valid.
     const IS_INVALID: Option<StaticMessage<2>>;
 }
 
+macro_rules! impl_check_text_field {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl CheckStaticMessageArg<$ty> for StaticText {
+                const IS_INVALID: Option<StaticMessage<2>> = None;
+            }
+
+            impl<const M: usize> CheckStaticMessageArg<$ty> for StaticMessage<M> {
+                const IS_INVALID: Option<StaticMessage<2>> = Some(gen_static_message!(
+                    "Error: A message with no arguments was expected in the language '{0}', ",
+                    "but received a message with {u:M} arguments for the key '{1}'. ",
+                    "Please check the message definition and ensure the correct number of arguments."
+                ));
+            }
+        )*
+    };
+}
+
+impl_check_text_field!(&'static str, Cow<'static, str>, String);
+
+macro_rules! impl_check_message_field {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl<const N: usize> CheckStaticMessageArg<$ty<N>> for StaticText {
+                const IS_INVALID: Option<StaticMessage<2>> = if N == 0 {
+                    None
+                } else {
+                    Some(gen_static_message!(
+                        "Error: A message with {u:N} arguments was expected in the language '{0}', ",
+                        "but received a message with no arguments for the key '{1}'. ",
+                        "Please check the message definition and ensure the correct number of arguments."
+                    ))
+                };
+            }
+
+            impl<const N: usize, const M: usize> CheckStaticMessageArg<$ty<N>> for StaticMessage<M> {
+                const IS_INVALID: Option<StaticMessage<2>> = if N == M {
+                    None
+                } else {
+                    Some(gen_static_message!(
+                        "Error: A message with {u:N} arguments was expected in the language '{0}', ",
+                        "but received a message with {u:M} arguments for the key '{1}'. ",
+                        "Please check the message definition and ensure the correct number of arguments."
+                    ))
+                };
+            }
+        )*
+    };
+}
+
+impl_check_message_field!(StaticMessage, AllocMessage);
+
+macro_rules! impl_check_unused_args {
+    (texts: $($ty:ty),* $(,)?) => {
+        $(
+            impl<T: CheckStaticMessageArg<$ty>> CheckStaticMessageArg<$ty> for UnusedArgs<T> {
+                const IS_INVALID: Option<StaticMessage<2>> = T::IS_INVALID;
+            }
+        )*
+    };
+    (messages: $($ty:ident),* $(,)?) => {
+        $(
+            impl<const N: usize> CheckStaticMessageArg<$ty<N>> for UnusedArgs<StaticText> {
+                const IS_INVALID: Option<StaticMessage<2>> = None;
+            }
+
+            impl<const N: usize, const M: usize> CheckStaticMessageArg<$ty<N>>
+                for UnusedArgs<StaticMessage<M>>
+            {
+                const IS_INVALID: Option<StaticMessage<2>> = if M <= N {
+                    None
+                } else {
+                    Some(gen_static_message!(
+                        "Error: A message with at most {u:N} arguments was expected in the language '{0}', ",
+                        "but received a message with {u:M} arguments for the key '{1}'. ",
+                        "Please check the message definition and ensure the correct number of arguments."
+                    ))
+                };
+            }
+        )*
+    };
+}
+
+impl_check_unused_args!(texts: &'static str, Cow<'static, str>, String);
+impl_check_unused_args!(messages: StaticMessage, AllocMessage);
+
+// check_static_message_arg creates a StaticMessage<N> from the formats of a message,
+// which relies on StaticMessage<N> and UnusedArgs<StaticMessage<M>> being transparent wrappers
+// of their formats for every N and M.
+const _: () = {
+    use std::mem::{align_of, size_of};
+
+    type Formats = &'static [RefMessageFormat<'static>];
+    assert!(size_of::<StaticMessage<0>>() == size_of::<Formats>());
+    assert!(align_of::<StaticMessage<0>>() == align_of::<Formats>());
+    assert!(size_of::<StaticMessage<12>>() == size_of::<Formats>());
+    assert!(align_of::<StaticMessage<12>>() == align_of::<Formats>());
+    assert!(size_of::<UnusedArgs<StaticMessage<12>>>() == size_of::<Formats>());
+    assert!(align_of::<UnusedArgs<StaticMessage<12>>>() == align_of::<Formats>());
+};
+
 /// Checks if the message argument is valid.
 /// If the argument is invalid, a panic is raised with a detailed error message.
-/// Otherwise, the argument is returned.
-/// 
+/// Otherwise, the argument is converted to the type of the field.
+///
+/// `String` and `AllocMessage<N>` fields cannot be created in a constant,
+/// so they raise a panic that asks for a `static`, which uses [`static_message_arg`] instead.
+///
 /// # Safety
 /// This function uses unsafe transmute operations, but only after compile-time validation
 /// that ensures the From and To types are compatible. The CheckStaticMessageArg trait
@@ -45,56 +313,274 @@ pub const fn check_static_message_arg<To, From>(
     from: From,
 ) -> To
 where
+    To: FromStaticMessage,
     From: CheckStaticMessageArg<To>,
 {
+    const REQUIRES_STATIC: StaticMessage<3> = gen_static_message!(
+        "Error: The field of type {2} for the key '{1}' in the language '{0}' is allocated, ",
+        "which requires def_local_fmt to define a static with `name = static NAME`."
+    );
+
     if let Some(message) = From::IS_INVALID {
         panic_builder!(message, [lang], [key]);
     }
-    
-    // SAFETY: The CheckStaticMessageArg trait ensures that From and To types
-    // are compatible for this conversion. The trait implementation validates
-    // that the size and layout requirements are met at compile time.
-    // 
-    // For the specific cases implemented:
-    // - &'static str -> &'static str: identity conversion, always safe
-    // - StaticMessage<N> -> StaticMessage<M> where N == M: same type, safe
-    // 
-    // The ManuallyDrop wrapper prevents double-drops while preserving the
-    // memory layout for the transmute operation.
-    unsafe { 
-        use std::mem::{ManuallyDrop, transmute_copy};
-        transmute_copy::<ManuallyDrop<From>, To>(&ManuallyDrop::new(from))
-    }
-}
-
-impl CheckStaticMessageArg<&'static str> for &'static str {
-    const IS_INVALID: Option<StaticMessage<2>> = None;
-}
-
-impl<const N: usize, const M: usize> CheckStaticMessageArg<StaticMessage<N>> for StaticMessage<M> {
-    const IS_INVALID: Option<StaticMessage<2>> = if N == M {
-        None
-    } else {
-        Some(gen_static_message!(
-            "Error: A message with {u:M} arguments was expected in the language '{0}', ",
-            "but received a message with {u:N} arguments for the key '{1}'. ",
-            "Please check the message definition and ensure the correct number of arguments."
-        ))
+
+    use std::mem::{transmute_copy, ManuallyDrop};
+
+    macro_rules! convert {
+        ($value:expr) => {{
+            let value = ManuallyDrop::new($value);
+            // SAFETY: FromStaticMessage is sealed, and `To::KIND` names the type of `value`,
+            // so `To` and the type of `value` are the same type.
+            unsafe { transmute_copy::<ManuallyDrop<_>, To>(&value) }
+        }};
+    }
+
+    // Creates the field from the formats of a message, when `To::KIND` is Static.
+    macro_rules! message {
+        ($formats:expr) => {{
+            let formats: &'static [RefMessageFormat<'static>] = $formats;
+            // SAFETY: `To::KIND` is Static, so `To` is a StaticMessage<N>,
+            // which is a transparent wrapper of its formats, as asserted above.
+            // IS_INVALID ensures that the formats use no more than N arguments.
+            unsafe { transmute_copy::<&'static [RefMessageFormat<'static>], To>(&formats) }
+        }};
+    }
+
+    let from = ManuallyDrop::new(from);
+    macro_rules! text {
+        () => {
+            // SAFETY: `From::IS_TEXT` is true for StaticText and UnusedArgs<StaticText>,
+            // which is transparent, while the other types implementing Sealed are messages.
+            unsafe { transmute_copy::<ManuallyDrop<From>, StaticText>(&from) }
+        };
+    }
+
+    match To::KIND {
+        MessageKind::Str => convert!(text!().text),
+        MessageKind::Cow => convert!(Cow::<'static, str>::Borrowed(text!().text)),
+        // IS_INVALID ensures that a StaticText is loaded into a StaticMessage<0>,
+        // and that a StaticMessage<M> is loaded into a StaticMessage<M>, unless it is
+        // wrapped in UnusedArgs, whose message uses fewer placeholders than the field,
+        // as a message created by RefMessage::new_with_unused_args does.
+        MessageKind::Static if From::IS_TEXT => message!(text!().message.formats()),
+        MessageKind::Static => {
+            // SAFETY: `From::IS_TEXT` is false for StaticMessage<M> and UnusedArgs<StaticMessage<M>>,
+            // which are transparent wrappers of their formats.
+            let formats = unsafe {
+                transmute_copy::<ManuallyDrop<From>, &'static [RefMessageFormat<'static>]>(&from)
+            };
+            message!(formats)
+        }
+        MessageKind::String | MessageKind::Alloc => {
+            let name = To::NAME;
+            panic_builder!(REQUIRES_STATIC, [lang], [key], [name])
+        }
+    }
+}
+
+/// A message or scalar of a language file, defined by the `def_local_fmt` macro
+/// for every field of a `static`, along with its language and key.
+#[doc(hidden)]
+pub trait StaticArg {
+    const LANG: &'static str;
+    const KEY: &'static str;
+    type Value;
+    const VALUE: Self::Value;
+}
+
+/// Converts a message to the type of the field when the messages of a `static` are first used.
+///
+/// The number of arguments is checked at compile time like [`check_static_message_arg`].
+pub fn static_message_arg<A, To>() -> To
+where
+    A: StaticArg,
+    A::Value: CheckStaticMessageArg<To>,
+    To: FromStaticMessage,
+{
+    const {
+        if let Some(message) = <A::Value as CheckStaticMessageArg<To>>::IS_INVALID {
+            let (lang, key) = (A::LANG, A::KEY);
+            panic_builder!(message, [lang], [key]);
+        }
+    }
+
+    let from = A::VALUE;
+    // SAFETY: IS_INVALID ensures that the message has the number of arguments of the field.
+    unsafe { To::from_message(from.text(), from.formats()) }
+}
+
+/// A number or boolean found in a language file.
+///
+/// The `def_local_fmt` macro emits a `Scalar` for every value that is not a string,
+/// and [`check_scalar_arg`] converts it to the type of the field at compile time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scalar {
+    /// The value as written in the message, used for `&'static str` fields.
+    pub text: &'static str,
+    pub value: ScalarValue,
+}
+
+/// The value of a [`Scalar`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScalarValue {
+    Bool(bool),
+    /// A number, with `int` set when the number is an integer.
+    /// The float values are computed by the macro, so that no float arithmetic is needed in const.
+    Number {
+        int: Option<i128>,
+        f32: f32,
+        f64: f64,
+    },
+}
+
+/// The kind of a type that a [`Scalar`] can be converted to.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarKind {
+    Str,
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    F32,
+    F64,
+}
+
+/// Trait for the field types that a [`Scalar`] can be converted to.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait FromScalar: scalar_sealed::Sealed {
+    #[doc(hidden)]
+    const KIND: ScalarKind;
+    /// The name of the type, used in the error messages.
+    const NAME: &'static str;
+}
+
+mod scalar_sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_from_scalar {
+    ($($ty:ty => $kind:ident),* $(,)?) => {
+        $(
+            impl scalar_sealed::Sealed for $ty {}
+            impl FromScalar for $ty {
+                const KIND: ScalarKind = ScalarKind::$kind;
+                const NAME: &'static str = stringify!($ty);
+            }
+        )*
     };
 }
 
-impl<const N: usize> CheckStaticMessageArg<&'static str> for StaticMessage<N> {
-    const IS_INVALID: Option<StaticMessage<2>> = Some(gen_static_message!(
-        "Error: A message with {u:N} arguments was expected in the language '{0}', ",
-        "but received a message with no arguments for the key '{1}'. ",
-        "Please check the message definition and ensure the correct number of arguments."
-    ));
+impl_from_scalar!(
+    &'static str => Str,
+    bool => Bool,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    usize => Usize,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    isize => Isize,
+    f32 => F32,
+    f64 => F64,
+);
+
+/// Converts a scalar of a language file to the type of the field.
+/// If the scalar does not fit the type, a panic is raised with a detailed error message.
+#[track_caller]
+pub const fn check_scalar_arg<To: FromScalar>(
+    lang: &'static str,
+    key: &'static str,
+    from: Scalar,
+) -> To {
+    const MISMATCH: StaticMessage<4> = gen_static_message!(
+        "Error: A value of type {2} was expected in the language '{0}', ",
+        "but received '{3}' for the key '{1}'. ",
+        "Please check the message definition and ensure the value fits the field."
+    );
+
+    macro_rules! convert {
+        ($value:expr) => {{
+            let value = $value;
+            // SAFETY: FromScalar is sealed, and `To::KIND` names the type of `value`,
+            // so `To` and the type of `value` are the same type.
+            unsafe { std::mem::transmute_copy(&value) }
+        }};
+    }
+    macro_rules! int {
+        ($ty:ty) => {
+            match from.value {
+                ScalarValue::Number { int: Some(int), .. }
+                    if int >= <$ty>::MIN as i128
+                        && (int < 0 || int as u128 <= <$ty>::MAX as u128) =>
+                {
+                    convert!(int as $ty)
+                }
+                _ => {
+                    let (name, text) = (To::NAME, from.text);
+                    panic_builder!(MISMATCH, [lang], [key], [name], [text])
+                }
+            }
+        };
+    }
+
+    match To::KIND {
+        ScalarKind::Str => convert!(from.text),
+        ScalarKind::Bool => match from.value {
+            ScalarValue::Bool(value) => convert!(value),
+            ScalarValue::Number { .. } => {
+                let (name, text) = (To::NAME, from.text);
+                panic_builder!(MISMATCH, [lang], [key], [name], [text])
+            }
+        },
+        ScalarKind::U8 => int!(u8),
+        ScalarKind::U16 => int!(u16),
+        ScalarKind::U32 => int!(u32),
+        ScalarKind::U64 => int!(u64),
+        ScalarKind::U128 => int!(u128),
+        ScalarKind::Usize => int!(usize),
+        ScalarKind::I8 => int!(i8),
+        ScalarKind::I16 => int!(i16),
+        ScalarKind::I32 => int!(i32),
+        ScalarKind::I64 => int!(i64),
+        ScalarKind::I128 => int!(i128),
+        ScalarKind::Isize => int!(isize),
+        ScalarKind::F32 | ScalarKind::F64 => match from.value {
+            ScalarValue::Number { f32, f64, .. } => match To::KIND {
+                ScalarKind::F32 => convert!(f32),
+                _ => convert!(f64),
+            },
+            ScalarValue::Bool(_) => {
+                let (name, text) = (To::NAME, from.text);
+                panic_builder!(MISMATCH, [lang], [key], [name], [text])
+            }
+        },
+    }
 }
 
-impl<const N: usize> CheckStaticMessageArg<StaticMessage<N>> for &'static str {
-    const IS_INVALID: Option<StaticMessage<2>> = Some(gen_static_message!(
-        "Error: A message with {u:N} arguments was expected in the language '{0}', ",
-        "but received a message with no arguments for the key '{1}'. ",
-        "Please check the message definition and ensure the correct number of arguments."
-    ));
+/// Converts a scalar of a language file to the type of the field of a `static`.
+///
+/// The scalar is checked at compile time like [`check_scalar_arg`].
+pub fn static_scalar_arg<A, To>() -> To
+where
+    A: StaticArg<Value = Scalar>,
+    To: FromScalar,
+{
+    const { check_scalar_arg::<To>(A::LANG, A::KEY, A::VALUE) }
 }
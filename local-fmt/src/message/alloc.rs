@@ -1,6 +1,9 @@
 use std::{fmt::Display, str::FromStr};
 
-use super::CreateMessageError;
+use super::{
+    format_segments, write_default, write_escaped, write_segments, CreateMessageError, Message,
+    ParseErrorKind, RefMessageFormat,
+};
 
 /// Represents a format for an allocatable message, which can be either text or a placeholder.
 ///
@@ -18,19 +21,90 @@ use super::CreateMessageError;
 pub enum AllocMessageFormat {
     AllocText(String),
     Placeholder(usize),
+    /// A placeholder written as `{1|anonymous}`, rendered as its default text
+    /// when the argument is empty.
+    PlaceholderDefault(usize, String),
+    /// The start of a conditional section written as `{1? ({1})}`, whose segments
+    /// up to the matching [`AllocMessageFormat::SectionEnd`] are only rendered
+    /// when the argument is not empty.
+    SectionStart(usize),
+    /// The end of a conditional section.
+    SectionEnd,
+}
+
+impl AllocMessageFormat {
+    /// Returns the number of the argument this segment uses, if any.
+    pub fn arg(&self) -> Option<usize> {
+        self.as_ref_format().arg()
+    }
+
+    /// Borrows the segment as a [`RefMessageFormat`].
+    pub fn as_ref_format(&self) -> RefMessageFormat<'_> {
+        match self {
+            AllocMessageFormat::AllocText(text) => RefMessageFormat::RefText(text),
+            AllocMessageFormat::Placeholder(n) => RefMessageFormat::Placeholder(*n),
+            AllocMessageFormat::PlaceholderDefault(n, text) => {
+                RefMessageFormat::PlaceholderDefault(*n, text)
+            }
+            AllocMessageFormat::SectionStart(n) => RefMessageFormat::SectionStart(*n),
+            AllocMessageFormat::SectionEnd => RefMessageFormat::SectionEnd,
+        }
+    }
 }
 
 impl Display for AllocMessageFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AllocMessageFormat::AllocText(text) => write!(f, "{}", text),
+            AllocMessageFormat::AllocText(text) => write_escaped(f, text, false),
             AllocMessageFormat::Placeholder(n) => write!(f, "{{{}}}", n),
+            AllocMessageFormat::PlaceholderDefault(n, text) => {
+                write!(f, "{{{}|", n)?;
+                write_default(f, text)?;
+                f.write_str("}")
+            }
+            AllocMessageFormat::SectionStart(n) => write!(f, "{{{}?", n),
+            AllocMessageFormat::SectionEnd => f.write_str("}"),
         }
     }
 }
 
 /// A message format that can be allocated with a fixed number of placeholders.
 ///
+/// # Syntax
+///
+/// A message is parsed by [`FromStr`], and written back by [`Display`] and `Serialize`,
+/// with the same syntax as `gen_static_message!` and the files of `def_local_fmt!`:
+///
+/// * `{0}`, `{1}`, ... are placeholders for the arguments.
+/// * `{{` and `}}` are a literal `{` and `}`. A `}` that does not close a placeholder
+///   is also a literal `}`.
+/// * `\{`, `\}` and `\\` are also a literal `{`, `}` and `\`.
+///   A backslash before any other character is kept as it is.
+/// * `{1|anonymous}` is a placeholder rendered as `anonymous` when the argument is empty.
+///   Its default text ends at the first `}`, and can contain `\{`, `\}` and `\\`.
+/// * `{1? ({1})}` is a conditional section, rendered only when the argument is not empty.
+///   The section ends at the first `}` that does not close a placeholder,
+///   so a literal `}` in a section is written `\}`. Sections can be nested.
+///
+/// [`Display`] escapes the texts with `{{`, `}}` and `\\`, so a message always parses back
+/// to an equal message.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use local_fmt::AllocMessage;
+///
+/// let message = AllocMessage::<1>::from_str("{{{0}}} \\{0}").unwrap();
+/// assert_eq!(message.format(&["name"]), "{name} {0}");
+///
+/// let text = message.to_string();
+/// assert_eq!(text, "{{{0}}} {{0}}");
+/// assert_eq!(AllocMessage::<1>::from_str(&text).unwrap(), message);
+///
+/// let message = AllocMessage::<2>::from_str("Signed in as {0|guest}{1? ({1})}").unwrap();
+/// assert_eq!(message.format(&["Alice", "admin"]), "Signed in as Alice (admin)");
+/// assert_eq!(message.format(&["", ""]), "Signed in as guest");
+/// ```
+///
 /// # Examples
 ///
 /// ```rust
@@ -76,7 +150,12 @@ impl<const N: usize> AllocMessage<N> {
 
     /// Creates a new `AllocMessage` with format checking.
     ///
-    /// Returns an error if the format is invalid, such as missing placeholders.
+    /// Like [`RefMessage::new`](crate::RefMessage::new), returns [`CreateMessageError::InvalidNumber`]
+    /// for a placeholder number of `N` or more, [`CreateMessageError::WithoutNumber`]
+    /// for a number below `N` that no placeholder uses, and [`CreateMessageError::UnbalancedSection`]
+    /// for a conditional section that is not closed or not opened.
+    /// Adjacent texts are merged and empty texts are removed, so that the message
+    /// equals the message parsed back from its [`Display`].
     ///
     /// # Examples
     ///
@@ -90,35 +169,47 @@ impl<const N: usize> AllocMessage<N> {
     ///
     /// let formatted = result.format(&["world"]);
     /// assert_eq!(formatted, "Hello, world");
+    ///
+    /// let result = AllocMessage::<1>::new(vec![AllocMessageFormat::Placeholder(1)]);
+    /// assert_eq!(result, Err(CreateMessageError::InvalidNumber { number: 1, n: 1 }));
+    ///
+    /// let result = AllocMessage::<1>::new(vec![AllocMessageFormat::AllocText(String::from("Hello"))]);
+    /// assert_eq!(result, Err(CreateMessageError::WithoutNumber { number: 0, n: 1 }));
     /// ```
     pub fn new(format: Vec<AllocMessageFormat>) -> Result<Self, CreateMessageError> {
-        let mut numbers = Vec::new();
-
-        let mut current = 0;
-
-        while format.len() > current {
-            if let AllocMessageFormat::Placeholder(n) = format[current] {
-                if n >= numbers.len() {
-                    numbers.resize_with(n + 1, Default::default);
-                }
-                numbers[n] = true;
-            }
-            current += 1;
-        }
-
-        let mut current = 0;
-
-        while numbers.len() > current {
-            if !numbers[current] {
-                return Err(CreateMessageError::WithoutNumber {
-                    number: current,
-                    n: N,
-                });
-            }
-            current += 1;
-        }
+        check_numbers::<N>(&format, false)?;
+        Ok(Self {
+            format: merge_texts(format),
+        })
+    }
 
-        Ok(Self { format })
+    /// Creates a new `AllocMessage` like [`AllocMessage::new`], but allows some arguments below `N`
+    /// to be unused, as in a language that omits an argument of a message.
+    ///
+    /// The number of arguments is kept by `N`, so the message is displayed as its text alone,
+    /// which [`FromStr`] rejects as it does not use every argument.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use local_fmt::{AllocMessage, AllocMessageFormat};
+    ///
+    /// let message = AllocMessage::<1>::new_with_unused_args(vec![
+    ///     AllocMessageFormat::AllocText(String::from("Hello!")),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(message.format(&["Rust"]), "Hello!");
+    /// assert_eq!(message.to_string(), "Hello!");
+    /// assert!(AllocMessage::<1>::from_str(&message.to_string()).is_err());
+    /// ```
+    pub fn new_with_unused_args(
+        format: Vec<AllocMessageFormat>,
+    ) -> Result<Self, CreateMessageError> {
+        check_numbers::<N>(&format, true)?;
+        Ok(Self {
+            format: merge_texts(format),
+        })
     }
 
     pub fn new_panic(format: Vec<AllocMessageFormat>) -> Self {
@@ -148,16 +239,7 @@ impl<const N: usize> AllocMessage<N> {
     /// assert_eq!(formatted, "Hello, world");
     /// ```
     pub fn format(&self, args: &[&str; N]) -> String {
-        let mut result = String::new();
-
-        for format in &self.format {
-            match format {
-                AllocMessageFormat::AllocText(text) => result.push_str(text),
-                AllocMessageFormat::Placeholder(n) => result.push_str(args[*n]),
-            }
-        }
-
-        result
+        format_segments(self.segments(), args)
     }
 
     /// Returns the number of format elements in this message.
@@ -170,7 +252,7 @@ impl<const N: usize> AllocMessage<N> {
     /// use std::str::FromStr;
     ///
     /// let msg = AllocMessage::<2>::from_str("Hello {0}, welcome to {1}!").unwrap();
-    /// assert_eq!(msg.len(), 4); // "Hello ", {0}, ", welcome to ", {1}, "!"
+    /// assert_eq!(msg.len(), 5); // "Hello ", {0}, ", welcome to ", {1}, "!"
     /// ```
     pub fn len(&self) -> usize {
         self.format.len()
@@ -210,111 +292,285 @@ impl<const N: usize> AllocMessage<N> {
 
 impl<const N: usize> Display for AllocMessage<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for format in &self.format {
-            write!(f, "{}", format)?;
-        }
-        Ok(())
+        write_segments(f, self.segments())
+    }
+}
+
+impl<const N: usize> AllocMessage<N> {
+    /// Parses a message like [`FromStr`], but collects every syntax error of the text
+    /// instead of stopping at the first one.
+    ///
+    /// After an error in a placeholder, parsing resumes after its closing `}`.
+    /// Missing placeholder numbers are only reported when the text has no syntax error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use local_fmt::{AllocMessage, CreateMessageError, ParseErrorKind};
+    ///
+    /// let errors = AllocMessage::<2>::from_str_all("Hello {0a}, {} and {1").unwrap_err();
+    /// assert_eq!(
+    ///     errors,
+    ///     vec![
+    ///         CreateMessageError::Parse {
+    ///             offset: 8,
+    ///             snippet: "{0a}".to_string(),
+    ///             kind: ParseErrorKind::UnexpectedChar('a'),
+    ///         },
+    ///         CreateMessageError::Parse {
+    ///             offset: 12,
+    ///             snippet: "{}".to_string(),
+    ///             kind: ParseErrorKind::EmptyPlaceholder,
+    ///         },
+    ///         CreateMessageError::Parse {
+    ///             offset: 19,
+    ///             snippet: "{1".to_string(),
+    ///             kind: ParseErrorKind::UnclosedPlaceholder,
+    ///         },
+    ///     ]
+    /// );
+    /// ```
+    pub fn from_str_all(s: &str) -> Result<Self, Vec<CreateMessageError>> {
+        let formats = parse_formats(s, true)?;
+        Self::new(formats).map_err(|error| vec![error])
     }
 }
 
 impl<const N: usize> FromStr for AllocMessage<N> {
     type Err = CreateMessageError;
 
+    /// Parses a message, returning its first error.
+    /// See [`AllocMessage::from_str_all`] to collect every error instead.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut formats = Vec::<AllocMessageFormat>::new();
+        let formats = parse_formats(s, false).map_err(|mut errors| errors.swap_remove(0))?;
+        Self::new(formats)
+    }
+}
+
+/// Checks that the placeholders of `format` are numbered from 0 to N-1, every number being used
+/// unless `allow_unused` is set, and that its conditional sections are balanced.
+fn check_numbers<const N: usize>(
+    format: &[AllocMessageFormat],
+    allow_unused: bool,
+) -> Result<(), CreateMessageError> {
+    let mut numbers = [false; N];
 
-        let mut buffer = Vec::<u8>::new();
+    for item in format {
+        if let Some(n) = item.arg() {
+            if n >= N {
+                return Err(CreateMessageError::InvalidNumber { number: n, n: N });
+            }
+            numbers[n] = true;
+        }
+    }
 
-        let mut bytes = s.bytes();
+    if let Some(number) = numbers
+        .iter()
+        .position(|used| !used)
+        .filter(|_| !allow_unused)
+    {
+        return Err(CreateMessageError::WithoutNumber { number, n: N });
+    }
 
-        while let Some(byte) = bytes.next() {
-            match byte {
-                b'{' => {
-                    if !buffer.is_empty() {
-                        // SAFETY: buffer only contains UTF-8 bytes from the original input string
-                        // which was already validated to be valid UTF-8 when converted from &str
-                        formats.push(AllocMessageFormat::AllocText(unsafe {
-                            String::from_utf8_unchecked(std::mem::take(&mut buffer))
-                        }));
-                    }
+    check_sections(format)
+}
+
+/// Checks that every conditional section of `format` is closed, and every end closes a section.
+pub(super) fn check_sections(format: &[AllocMessageFormat]) -> Result<(), CreateMessageError> {
+    let mut depth = 0usize;
+    for item in format {
+        match item {
+            AllocMessageFormat::SectionStart(_) => depth += 1,
+            AllocMessageFormat::SectionEnd => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or(CreateMessageError::UnbalancedSection)?
+            }
+            _ => {}
+        }
+    }
+    if depth == 0 {
+        Ok(())
+    } else {
+        Err(CreateMessageError::UnbalancedSection)
+    }
+}
+
+/// Merges adjacent texts and removes empty texts.
+pub(super) fn merge_texts(format: Vec<AllocMessageFormat>) -> Vec<AllocMessageFormat> {
+    let mut merged = Vec::<AllocMessageFormat>::with_capacity(format.len());
+    for item in format {
+        match (merged.last_mut(), item) {
+            (_, AllocMessageFormat::AllocText(text)) if text.is_empty() => {}
+            (Some(AllocMessageFormat::AllocText(last)), AllocMessageFormat::AllocText(text)) => {
+                last.push_str(&text)
+            }
+            (_, item) => merged.push(item),
+        }
+    }
+    merged
+}
+
+/// Parses the text of a message, stopping at the first error unless `all_errors` is set.
+/// The returned errors are never empty.
+pub(super) fn parse_formats(
+    s: &str,
+    all_errors: bool,
+) -> Result<Vec<AllocMessageFormat>, Vec<CreateMessageError>> {
+    let mut formats = Vec::<AllocMessageFormat>::new();
+    let mut errors = Vec::<CreateMessageError>::new();
+
+    let mut text = String::new();
+    // The start and end of the conditional sections that are not closed yet.
+    let mut sections = Vec::<(usize, usize)>::new();
+
+    let mut chars = s.char_indices().peekable();
 
-                    let mut number = None::<usize>;
-
-                    loop {
-                        match bytes.next() {
-                            Some(byte) => match byte {
-                                b'}' => {
-                                    if let Some(number) = number {
-                                        formats.push(AllocMessageFormat::Placeholder(number));
-                                        break;
-                                    } else {
-                                        return Err(CreateMessageError::EmptyPlaceholder);
-                                    }
-                                }
-                                b'0'..=b'9' => {
-                                    let mut num = number.unwrap_or(0);
-                                    // Check for potential overflow before multiplication
-                                    if num > usize::MAX / 10 {
-                                        return Err(CreateMessageError::InvalidNumber {
-                                            number: num,
-                                            n: N,
-                                        });
-                                    }
-                                    num *= 10;
-                                    let digit = (byte - b'0') as usize;
-                                    // Check for potential overflow before addition
-                                    if num > usize::MAX - digit {
-                                        return Err(CreateMessageError::InvalidNumber {
-                                            number: num,
-                                            n: N,
-                                        });
-                                    }
-                                    num += digit;
-                                    number = Some(num);
-                                }
-                                _ => match number {
-                                    Some(number) => {
-                                        return Err(CreateMessageError::InvalidNumber {
-                                            number,
-                                            n: N,
-                                        });
-                                    }
-                                    None => {
-                                        return Err(CreateMessageError::EmptyPlaceholder);
-                                    }
-                                },
-                            },
-                            None => {
-                                return Err(CreateMessageError::EmptyPlaceholder);
-                            }
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '{' if chars.next_if(|&(_, c)| c == '{').is_some() => text.push('{'),
+            '{' => {
+                if !text.is_empty() {
+                    formats.push(AllocMessageFormat::AllocText(std::mem::take(&mut text)));
+                }
+
+                let close = placeholder_end(s, offset);
+                let end = close.unwrap_or(s.len());
+                while chars.next_if(|&(i, _)| i < end).is_some() {}
+
+                match parse_placeholder(s, offset, end, close.is_some()) {
+                    Ok(format) => {
+                        if let AllocMessageFormat::SectionStart(_) = format {
+                            sections.push((offset, end));
                         }
+                        formats.push(format);
                     }
-                }
-                b'\\' => {
-                    if let Some(byte) = bytes.next() {
-                        match byte {
-                            b'{' => buffer.push(b'{'),
-                            _ => {
-                                buffer.push(b'\\');
-                                buffer.push(byte);
-                            }
+                    Err(error) => {
+                        errors.push(error);
+                        if !all_errors {
+                            break;
                         }
-                    } else {
-                        buffer.push(b'\\');
                     }
                 }
-                _ => buffer.push(byte),
             }
+            '}' if !sections.is_empty() => {
+                if !text.is_empty() {
+                    formats.push(AllocMessageFormat::AllocText(std::mem::take(&mut text)));
+                }
+                sections.pop();
+                formats.push(AllocMessageFormat::SectionEnd);
+            }
+            '}' if chars.next_if(|&(_, c)| c == '}').is_some() => text.push('}'),
+            '\\' => match chars.next_if(|&(_, c)| matches!(c, '{' | '}' | '\\')) {
+                Some((_, c)) => text.push(c),
+                None => text.push('\\'),
+            },
+            c => text.push(c),
         }
+    }
+
+    if !text.is_empty() {
+        formats.push(AllocMessageFormat::AllocText(text));
+    }
+
+    if errors.is_empty() || all_errors {
+        errors.extend(
+            sections
+                .into_iter()
+                .map(|(start, end)| CreateMessageError::Parse {
+                    offset: start,
+                    snippet: s[start..end].to_string(),
+                    kind: ParseErrorKind::UnclosedSection,
+                }),
+        );
+    }
+
+    if errors.is_empty() {
+        Ok(formats)
+    } else {
+        Err(errors)
+    }
+}
 
-        if !buffer.is_empty() {
-            // SAFETY: buffer only contains UTF-8 bytes from the original input string
-            // which was already validated to be valid UTF-8 when converted from &str
-            formats.push(AllocMessageFormat::AllocText(unsafe {
-                String::from_utf8_unchecked(buffer)
-            }));
+/// Returns the end of the placeholder starting with the `{` at `start`, after its closing `}`,
+/// or after the `?` that starts a conditional section such as `{1?`.
+/// Returns `None` if the placeholder is not closed.
+fn placeholder_end(s: &str, start: usize) -> Option<usize> {
+    let mut chars = s[start + 1..].char_indices();
+    let mut digits = true;
+    let mut in_default = false;
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '}' => return Some(start + 1 + i + 1),
+            '?' if digits && i > 0 => return Some(start + 1 + i + 1),
+            '|' => in_default = true,
+            '\\' if in_default => {
+                chars.next();
+            }
+            _ => {}
         }
+        digits &= c.is_ascii_digit();
+    }
+    None
+}
 
-        Self::new(formats)
+/// Parses the placeholder `s[start..end]`, which starts with `{` and ends with the `}`
+/// or `?` found by [`placeholder_end`] if it is `closed`.
+fn parse_placeholder(
+    s: &str,
+    start: usize,
+    end: usize,
+    closed: bool,
+) -> Result<AllocMessageFormat, CreateMessageError> {
+    let snippet = &s[start..end];
+    let error = |offset: usize, kind: ParseErrorKind| CreateMessageError::Parse {
+        offset,
+        snippet: snippet.to_string(),
+        kind,
+    };
+
+    if !closed {
+        return Err(error(start, ParseErrorKind::UnclosedPlaceholder));
+    }
+    if let Some(number) = snippet[1..].strip_suffix('?') {
+        return number
+            .parse::<usize>()
+            .map(AllocMessageFormat::SectionStart)
+            .map_err(|_| error(start, ParseErrorKind::NumberOverflow));
+    }
+
+    let body = &snippet[1..snippet.len() - 1];
+    let (number, default) = match body.split_once('|') {
+        Some((number, default)) => (number, Some(default)),
+        None => (body, None),
+    };
+    if number.is_empty() {
+        return Err(error(start, ParseErrorKind::EmptyPlaceholder));
+    }
+    if let Some((i, c)) = number.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(error(start + 1 + i, ParseErrorKind::UnexpectedChar(c)));
+    }
+    let number = number
+        .parse::<usize>()
+        .map_err(|_| error(start, ParseErrorKind::NumberOverflow))?;
+    Ok(match default {
+        Some(default) => AllocMessageFormat::PlaceholderDefault(number, unescape_default(default)),
+        None => AllocMessageFormat::Placeholder(number),
+    })
+}
+
+/// Replaces `\{`, `\}` and `\\` in the default text of a placeholder.
+fn unescape_default(default: &str) -> String {
+    let mut text = String::with_capacity(default.len());
+    let mut chars = default.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next_if(|c| matches!(c, '{' | '}' | '\\')) {
+                Some(c) => text.push(c),
+                None => text.push('\\'),
+            },
+            c => text.push(c),
+        }
     }
+    text
 }
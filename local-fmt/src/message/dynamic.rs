@@ -0,0 +1,259 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{
+    check_sections, format_segments, merge_texts, parse_formats, write_segments, AllocMessage,
+    AllocMessageFormat, ArgCountError, CreateMessageError, RefMessage, RefMessageFormat,
+};
+
+/// A message whose number of arguments is only known at runtime.
+///
+/// Unlike [`AllocMessage<N>`], a `DynMessage` can hold any message, such as one loaded
+/// from a file provided by a translator, so that its number of arguments can be inspected
+/// before it is used or converted to a message with a fixed number of arguments.
+///
+/// The number of arguments is one more than the highest placeholder number,
+/// and every placeholder number below it must be used, unless the message is created by
+/// [`DynMessage::new_with_unused_args`] or converted from a message with a fixed number of arguments.
+/// The number of arguments is kept apart from the text, and is not part of its [`Display`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use local_fmt::{AllocMessage, ArgCountError, DynMessage};
+///
+/// let message = DynMessage::from_str("{0} has {1} new messages").unwrap();
+/// assert_eq!(message.args(), 2);
+///
+/// assert_eq!(message.format(&["Rust", "3"]).unwrap(), "Rust has 3 new messages");
+/// assert_eq!(
+///     message.format(&["Rust"]),
+///     Err(ArgCountError { expected: 2, given: 1 })
+/// );
+///
+/// let message = AllocMessage::<2>::try_from(message).unwrap();
+/// assert_eq!(message.format(&["Rust", "3"]), "Rust has 3 new messages");
+/// ```
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DynMessage {
+    format: Vec<AllocMessageFormat>,
+    args: usize,
+}
+
+impl DynMessage {
+    /// Creates a new `DynMessage`, whose number of arguments is taken from its placeholders.
+    ///
+    /// Returns an error if a placeholder number below the highest one is not used,
+    /// or if a conditional section is not balanced.
+    /// Adjacent texts are merged and empty texts are removed, like [`AllocMessage::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use local_fmt::{AllocMessageFormat, CreateMessageError, DynMessage};
+    ///
+    /// let message = DynMessage::new(vec![
+    ///     AllocMessageFormat::AllocText(String::from("Hello, ")),
+    ///     AllocMessageFormat::Placeholder(0),
+    /// ]).unwrap();
+    /// assert_eq!(message.args(), 1);
+    ///
+    /// let error = DynMessage::new(vec![AllocMessageFormat::Placeholder(1)]).unwrap_err();
+    /// assert_eq!(error, CreateMessageError::WithoutNumber { number: 0, n: 2 });
+    /// ```
+    pub fn new(format: Vec<AllocMessageFormat>) -> Result<Self, CreateMessageError> {
+        let mut numbers = Vec::<bool>::new();
+        for item in &format {
+            if let Some(n) = item.arg() {
+                if n >= numbers.len() {
+                    numbers.resize(n + 1, false);
+                }
+                numbers[n] = true;
+            }
+        }
+
+        let args = numbers.len();
+        if let Some(number) = numbers.iter().position(|used| !used) {
+            return Err(CreateMessageError::WithoutNumber { number, n: args });
+        }
+        check_sections(&format)?;
+
+        Ok(Self {
+            format: merge_texts(format),
+            args,
+        })
+    }
+
+    /// Creates a new `DynMessage` with `args` arguments, some of which may be unused,
+    /// like [`AllocMessage::new_with_unused_args`].
+    ///
+    /// Returns an error if a placeholder number is `args` or more,
+    /// or if a conditional section is not balanced.
+    /// The number of arguments is kept apart from the text, so the message is displayed
+    /// as its text alone, which parses back to a message with the arguments its placeholders use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use local_fmt::{AllocMessageFormat, DynMessage};
+    ///
+    /// let message = DynMessage::new_with_unused_args(
+    ///     vec![AllocMessageFormat::AllocText(String::from("Hello!"))],
+    ///     1,
+    /// ).unwrap();
+    /// assert_eq!(message.args(), 1);
+    /// assert_eq!(message.format(&["Rust"]).unwrap(), "Hello!");
+    /// assert_eq!(message.to_string(), "Hello!");
+    /// assert_eq!(DynMessage::from_str(&message.to_string()).unwrap().args(), 0);
+    /// ```
+    pub fn new_with_unused_args(
+        format: Vec<AllocMessageFormat>,
+        args: usize,
+    ) -> Result<Self, CreateMessageError> {
+        if let Some(number) = format.iter().filter_map(AllocMessageFormat::arg).max() {
+            if number >= args {
+                return Err(CreateMessageError::InvalidNumber { number, n: args });
+            }
+        }
+        check_sections(&format)?;
+
+        Ok(Self {
+            format: merge_texts(format),
+            args,
+        })
+    }
+
+    /// Parses a message like [`FromStr`], but collects every syntax error of the text.
+    /// See [`AllocMessage::from_str_all`].
+    pub fn from_str_all(s: &str) -> Result<Self, Vec<CreateMessageError>> {
+        let formats = parse_formats(s, true)?;
+        Self::new(formats).map_err(|error| vec![error])
+    }
+
+    /// Returns the number of arguments of this message.
+    pub fn args(&self) -> usize {
+        self.args
+    }
+
+    /// Formats the message with the provided arguments.
+    ///
+    /// Returns an error if the number of arguments is not [`DynMessage::args`].
+    pub fn format(&self, args: &[&str]) -> Result<String, ArgCountError> {
+        if args.len() != self.args {
+            return Err(ArgCountError {
+                expected: self.args,
+                given: args.len(),
+            });
+        }
+
+        Ok(format_segments(
+            self.format.iter().map(AllocMessageFormat::as_ref_format),
+            args,
+        ))
+    }
+
+    /// Returns the number of format elements in this message.
+    pub fn len(&self) -> usize {
+        self.format.len()
+    }
+
+    /// Returns true if this message has no format elements.
+    pub fn is_empty(&self) -> bool {
+        self.format.is_empty()
+    }
+
+    /// Returns a reference to the internal format elements.
+    pub fn formats(&self) -> &[AllocMessageFormat] {
+        &self.format
+    }
+
+    /// Returns the format elements borrowed as [`RefMessageFormat`]s,
+    /// from which [`RefMessage::new`] creates a message with the checked number of arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use local_fmt::{CreateMessageError, DynMessage, RefMessage};
+    ///
+    /// let message = DynMessage::from_str("Hello, {0}!").unwrap();
+    /// let formats = message.ref_formats();
+    ///
+    /// let message = RefMessage::<1>::new(&formats).unwrap();
+    /// assert_eq!(message.format(&["Rust"]), "Hello, Rust!");
+    ///
+    /// let error = RefMessage::<2>::new(&formats).unwrap_err();
+    /// assert_eq!(error, CreateMessageError::WithoutNumber { number: 1, n: 2 });
+    /// ```
+    pub fn ref_formats(&self) -> Vec<RefMessageFormat<'_>> {
+        self.format
+            .iter()
+            .map(AllocMessageFormat::as_ref_format)
+            .collect()
+    }
+}
+
+impl<const N: usize> From<AllocMessage<N>> for DynMessage {
+    fn from(message: AllocMessage<N>) -> Self {
+        Self {
+            format: message.formats().clone(),
+            args: N,
+        }
+    }
+}
+
+/// Converts a message with a fixed number of arguments,
+/// rendering its constants such as `{u:MAX}` as text. The message keeps its `N` arguments,
+/// even if it leaves some of them unused like [`RefMessage::new_with_unused_args`].
+impl<const N: usize> From<RefMessage<'_, N>> for DynMessage {
+    fn from(message: RefMessage<'_, N>) -> Self {
+        let format = message
+            .formats()
+            .iter()
+            .map(RefMessageFormat::to_alloc_format)
+            .collect();
+        Self {
+            format: merge_texts(format),
+            args: N,
+        }
+    }
+}
+
+/// Converts to a message with `N` arguments, failing if the message has another number of arguments.
+impl<const N: usize> TryFrom<DynMessage> for AllocMessage<N> {
+    type Error = CreateMessageError;
+
+    fn try_from(message: DynMessage) -> Result<Self, Self::Error> {
+        if message.args > N {
+            return Err(CreateMessageError::InvalidNumber {
+                number: message.args - 1,
+                n: N,
+            });
+        }
+        if message.args < N {
+            return Err(CreateMessageError::WithoutNumber {
+                number: message.args,
+                n: N,
+            });
+        }
+        // SAFETY: the placeholders are below N, as checked by DynMessage::new
+        // and DynMessage::new_with_unused_args.
+        Ok(unsafe { AllocMessage::new_unchecked(message.format) })
+    }
+}
+
+impl Display for DynMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_segments(f, self.format.iter().map(AllocMessageFormat::as_ref_format))
+    }
+}
+
+impl FromStr for DynMessage {
+    type Err = CreateMessageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let formats = parse_formats(s, false).map_err(|mut errors| errors.swap_remove(0))?;
+        Self::new(formats)
+    }
+}
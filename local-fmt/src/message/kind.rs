@@ -0,0 +1,95 @@
+use std::fmt::Display;
+
+use crate::LocalFormatArgs;
+
+/// A value accepted by an argument declared as `{0:number}` in the files of `def_local_fmt`.
+///
+/// The accessors generated with `accessors` take an `impl NumberArg` for such arguments,
+/// so that a count cannot be passed as free text.
+///
+/// This trait is implemented for the primitive integer and float types.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a number, but the argument is declared as a number",
+    label = "expected an integer or a float"
+)]
+pub trait NumberArg: Display {}
+
+macro_rules! impl_number_arg {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl NumberArg for $ty {}
+        )*
+    };
+}
+
+impl_number_arg!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl<T: NumberArg + ?Sized> NumberArg for &T {}
+
+/// A value accepted by an argument declared as `{0:date}` in the files of `def_local_fmt`.
+///
+/// The standard library has no date type to format, so this trait is implemented
+/// for the date types of an application, which decide how a date is displayed.
+///
+/// # Example
+/// ```
+/// use std::fmt::{self, Display};
+/// use local_fmt::DateArg;
+///
+/// struct Date {
+///     year: u16,
+///     month: u8,
+///     day: u8,
+/// }
+///
+/// impl Display for Date {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+///     }
+/// }
+///
+/// impl DateArg for Date {}
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a date, but the argument is declared as a date",
+    label = "expected a type implementing `DateArg`"
+)]
+pub trait DateArg: Display {}
+
+impl<T: DateArg + ?Sized> DateArg for &T {}
+
+/// A value accepted by an argument declared as `{0:msg}` in the files of `def_local_fmt`,
+/// which is another message formatted with its own arguments.
+///
+/// This trait is implemented for the [`LocalFormatArgs`] created by
+/// [`local_format_args!`](crate::local_format_args), whose number of arguments is checked
+/// against its message. A message without arguments, such as a `&'static str` field,
+/// is given as `local_format_args!(MESSAGES.units.unknown)`.
+/// The message is written directly into the outer message, without an intermediate `String`.
+///
+/// What is enforced is that the argument is a pre-formatted message rather than any `Display` value,
+/// which marks it as localized text at the call site. The text itself is not checked:
+/// a `&'static str` is a message without arguments, so `local_format_args!("far")` is accepted.
+///
+/// # Example
+/// ```
+/// use local_fmt::{gen_static_message, local_format_args, MessageArg, StaticMessage};
+///
+/// fn describe(unit: impl MessageArg) -> String {
+///     format!("The distance is {}.", unit)
+/// }
+///
+/// const KILOMETERS: StaticMessage<1> = gen_static_message!("{0} km");
+///
+/// assert_eq!(describe(local_format_args!(KILOMETERS, 42)), "The distance is 42 km.");
+/// assert_eq!(describe(local_format_args!("far")), "The distance is far.");
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a formatted message, but the argument is declared as a message",
+    label = "expected a message created by `local_format_args!`"
+)]
+pub trait MessageArg: Display {}
+
+impl<const N: usize> MessageArg for LocalFormatArgs<'_, N> {}
+
+impl<T: MessageArg + ?Sized> MessageArg for &T {}
@@ -6,6 +6,15 @@ pub use alloc::*;
 pub mod refer;
 pub use refer::*;
 
+pub mod dynamic;
+pub use dynamic::*;
+
+pub mod traits;
+pub use traits::*;
+
+pub mod kind;
+pub use kind::*;
+
 /// Represents errors that can occur when working with constant messages.
 ///
 /// This enum provides detailed error information for invalid or missing argument numbers
@@ -31,10 +40,124 @@ pub enum CreateMessageError {
     /// This can happen when a placeholder is found without a number.
     #[error("Empty placeholder found: a placeholder was opened but not closed properly. Ensure all placeholders are correctly formatted.")]
     EmptyPlaceholder,
+
+    /// Error indicating that the conditional sections of a message are not balanced.
+    ///
+    /// This error occurs when a [`RefMessageFormat::SectionStart`] has no matching
+    /// [`RefMessageFormat::SectionEnd`], or a `SectionEnd` has no matching `SectionStart`.
+    #[error("Unbalanced conditional section: every section opened with `{{N?` must be closed with `}}` exactly once.")]
+    UnbalancedSection,
+
+    /// Error indicating that the text of a message could not be parsed.
+    ///
+    /// `offset` is the byte offset of the error in the text, such as the offset of
+    /// an unexpected character, and `snippet` is the offending placeholder, from its `{`
+    /// to its `}` or to the end of the text if it is not closed.
+    #[error("Invalid message syntax at byte {offset}: {kind} in `{snippet}`.")]
+    Parse {
+        offset: usize,
+        snippet: String,
+        kind: ParseErrorKind,
+    },
+}
+
+/// Error indicating that a message was formatted with the wrong number of arguments.
+///
+/// This error occurs when the number of arguments of a message is only known at runtime,
+/// as with [`DynMessage`].
+#[derive(Debug, Clone, Copy, thiserror::Error, PartialEq, Eq)]
+#[error(
+    "Wrong number of arguments: the message takes {expected} arguments, but {given} were given."
+)]
+pub struct ArgCountError {
+    pub expected: usize,
+    pub given: usize,
+}
+
+/// The kind of a syntax error in the text of a message.
+#[derive(Debug, Clone, Copy, thiserror::Error, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A placeholder is opened with `{` but never closed with `}`.
+    #[error("the placeholder is not closed")]
+    UnclosedPlaceholder,
+    /// A placeholder has no number, as in `{}`.
+    #[error("the placeholder has no number")]
+    EmptyPlaceholder,
+    /// A placeholder contains a character other than a digit, as in `{name}`.
+    #[error("unexpected character {0:?} in the placeholder")]
+    UnexpectedChar(char),
+    /// The number of a placeholder does not fit in a `usize`.
+    #[error("the placeholder number is too large")]
+    NumberOverflow,
+    /// A conditional section is opened with `{N?` but never closed with `}`.
+    #[error("the conditional section is not closed")]
+    UnclosedSection,
+}
+
+/// Writes the text of a message with `{`, `}` and `\` escaped as `{{`, `}}` and `\\`,
+/// so that it parses back as the same text.
+///
+/// In a conditional section, where `}` closes the section, `}` is escaped as `\}` instead.
+pub(crate) fn write_escaped(
+    f: &mut std::fmt::Formatter<'_>,
+    text: &str,
+    in_section: bool,
+) -> std::fmt::Result {
+    let mut rest = text;
+    while let Some(index) = rest.find(['{', '}', '\\']) {
+        f.write_str(&rest[..index])?;
+        f.write_str(match rest.as_bytes()[index] {
+            b'{' => "{{",
+            b'}' if in_section => "\\}",
+            b'}' => "}}",
+            _ => "\\\\",
+        })?;
+        rest = &rest[index + 1..];
+    }
+    f.write_str(rest)
+}
+
+/// Writes the default text of a placeholder such as `{1|anonymous}`,
+/// with `{`, `}` and `\` escaped as `\{`, `\}` and `\\`.
+pub(crate) fn write_default(f: &mut std::fmt::Formatter<'_>, text: &str) -> std::fmt::Result {
+    let mut rest = text;
+    while let Some(index) = rest.find(['{', '}', '\\']) {
+        f.write_str(&rest[..index])?;
+        f.write_str("\\")?;
+        f.write_str(&rest[index..index + 1])?;
+        rest = &rest[index + 1..];
+    }
+    f.write_str(rest)
+}
+
+/// Writes the segments of a message, escaping the texts of conditional sections
+/// so that the message parses back as the same message.
+pub(crate) fn write_segments<'a>(
+    f: &mut std::fmt::Formatter<'_>,
+    segments: impl Iterator<Item = RefMessageFormat<'a>>,
+) -> std::fmt::Result {
+    let mut depth = 0usize;
+    for segment in segments {
+        match segment {
+            RefMessageFormat::RefText(text) => write_escaped(f, text, depth > 0)?,
+            RefMessageFormat::Char(c) => write_escaped(f, c.encode_utf8(&mut [0; 4]), depth > 0)?,
+            RefMessageFormat::SectionStart(n) => {
+                depth += 1;
+                write!(f, "{{{}?", n)?
+            }
+            RefMessageFormat::SectionEnd => {
+                depth = depth.saturating_sub(1);
+                f.write_str("}")?
+            }
+            segment => write!(f, "{}", segment)?,
+        }
+    }
+    Ok(())
 }
 
 impl CreateMessageError {
     #[track_caller]
+    #[allow(clippy::panic)]
     pub const fn panic(&self) -> ! {
         match self {
             Self::InvalidNumber { number, n } => {
@@ -62,9 +185,47 @@ impl CreateMessageError {
 
                 panic_builder!(MESSAGE, [u; *number], [u; *n])
             }
+            Self::UnbalancedSection => {
+                panic!("Unbalanced conditional section: every section opened with `{{N?` must be closed with `}}` exactly once.")
+            }
             Self::EmptyPlaceholder => {
                 panic!("Empty placeholder found: a placeholder was opened but not closed properly. Ensure all placeholders are correctly formatted.")
             }
+            Self::Parse {
+                offset,
+                kind: ParseErrorKind::UnexpectedChar(c),
+                ..
+            } => {
+                const MESSAGE: StaticMessage<2> = local_fmt::StaticMessage::new_panic(&[
+                    local_fmt::RefMessageFormat::RefText("Invalid message syntax at byte "),
+                    local_fmt::RefMessageFormat::Placeholder(0usize),
+                    local_fmt::RefMessageFormat::RefText(": unexpected character '"),
+                    local_fmt::RefMessageFormat::Placeholder(1usize),
+                    local_fmt::RefMessageFormat::RefText("' in the placeholder."),
+                ]);
+                let c = crate::const_char_to_str(*c);
+                let c = c.buffer();
+
+                panic_builder!(MESSAGE, [u; *offset], [b; c])
+            }
+            Self::Parse { offset, kind, .. } => {
+                const MESSAGE: StaticMessage<2> = local_fmt::StaticMessage::new_panic(&[
+                    local_fmt::RefMessageFormat::RefText("Invalid message syntax at byte "),
+                    local_fmt::RefMessageFormat::Placeholder(0usize),
+                    local_fmt::RefMessageFormat::RefText(": "),
+                    local_fmt::RefMessageFormat::Placeholder(1usize),
+                    local_fmt::RefMessageFormat::RefText("."),
+                ]);
+                let kind = match kind {
+                    ParseErrorKind::UnclosedPlaceholder => "the placeholder is not closed",
+                    ParseErrorKind::EmptyPlaceholder => "the placeholder has no number",
+                    ParseErrorKind::UnexpectedChar(_) => "unexpected character in the placeholder",
+                    ParseErrorKind::NumberOverflow => "the placeholder number is too large",
+                    ParseErrorKind::UnclosedSection => "the conditional section is not closed",
+                };
+
+                panic_builder!(MESSAGE, [u; *offset], [kind])
+            }
         }
     }
 }
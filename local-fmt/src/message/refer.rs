@@ -1,30 +1,93 @@
 use std::fmt::Display;
 
-use crate::{const_i128_to_str, const_u128_to_str, UtilBufWrapper};
+use crate::{
+    const_char_to_str, const_float_to_str, const_i128_to_str, const_u128_to_str, ConstFloat,
+    UtilBufWrapper,
+};
 
-use super::CreateMessageError;
+use super::{
+    format_segments, write_default, write_escaped, write_segments, AllocMessageFormat,
+    CreateMessageError,
+};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum RefMessageFormat<'a> {
     RefText(&'a str),
     UNumber(u128),
     INumber(i128),
+    Float(ConstFloat),
+    Char(char),
+    Bool(bool),
     Placeholder(usize),
+    /// A placeholder written as `{1|anonymous}`, rendered as its default text
+    /// when the argument is empty.
+    PlaceholderDefault(usize, &'a str),
+    /// The start of a conditional section written as `{1? ({1})}`, whose segments
+    /// up to the matching [`RefMessageFormat::SectionEnd`] are only rendered
+    /// when the argument is not empty.
+    SectionStart(usize),
+    /// The end of a conditional section.
+    SectionEnd,
+}
+
+impl RefMessageFormat<'_> {
+    /// Returns the number of the argument this segment uses, if any.
+    pub const fn arg(&self) -> Option<usize> {
+        match self {
+            RefMessageFormat::Placeholder(n)
+            | RefMessageFormat::PlaceholderDefault(n, _)
+            | RefMessageFormat::SectionStart(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Copies the segment into an [`AllocMessageFormat`], rendering a constant as text.
+    pub fn to_alloc_format(&self) -> AllocMessageFormat {
+        match self {
+            RefMessageFormat::RefText(text) => AllocMessageFormat::AllocText(text.to_string()),
+            RefMessageFormat::UNumber(n) => AllocMessageFormat::AllocText(n.to_string()),
+            RefMessageFormat::INumber(n) => AllocMessageFormat::AllocText(n.to_string()),
+            RefMessageFormat::Float(n) => AllocMessageFormat::AllocText(n.to_string()),
+            RefMessageFormat::Char(c) => AllocMessageFormat::AllocText(c.to_string()),
+            RefMessageFormat::Bool(b) => AllocMessageFormat::AllocText(b.to_string()),
+            RefMessageFormat::Placeholder(n) => AllocMessageFormat::Placeholder(*n),
+            RefMessageFormat::PlaceholderDefault(n, text) => {
+                AllocMessageFormat::PlaceholderDefault(*n, text.to_string())
+            }
+            RefMessageFormat::SectionStart(n) => AllocMessageFormat::SectionStart(*n),
+            RefMessageFormat::SectionEnd => AllocMessageFormat::SectionEnd,
+        }
+    }
 }
 
 impl Display for RefMessageFormat<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            RefMessageFormat::RefText(text) => write!(f, "{}", text),
+            RefMessageFormat::RefText(text) => write_escaped(f, text, false),
             RefMessageFormat::UNumber(n) => write!(f, "{}", n),
             RefMessageFormat::INumber(n) => write!(f, "{}", n),
+            RefMessageFormat::Float(n) => write!(f, "{}", n),
+            RefMessageFormat::Char(c) => write_escaped(f, c.encode_utf8(&mut [0; 4]), false),
+            RefMessageFormat::Bool(b) => write!(f, "{}", b),
             RefMessageFormat::Placeholder(n) => write!(f, "{{{}}}", n),
+            RefMessageFormat::PlaceholderDefault(n, text) => {
+                write!(f, "{{{}|", n)?;
+                write_default(f, text)?;
+                f.write_str("}")
+            }
+            RefMessageFormat::SectionStart(n) => write!(f, "{{{}?", n),
+            RefMessageFormat::SectionEnd => f.write_str("}"),
         }
     }
 }
 
 pub type StaticMessage<const N: usize> = RefMessage<'static, N>;
 
+/// A message borrowing its format elements, with `N` arguments.
+///
+/// The message is a transparent wrapper of its format elements, so that its layout
+/// does not depend on `N`.
+#[repr(transparent)]
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RefMessage<'a, const N: usize> {
     formats: &'a [RefMessageFormat<'a>],
@@ -38,66 +101,82 @@ impl<'a, const N: usize> RefMessage<'a, N> {
     }
 
     pub const fn new(formats: &'a [RefMessageFormat<'a>]) -> Result<Self, CreateMessageError> {
-        let mut numbers = [false; N];
-
-        let mut current = 0;
-
-        while formats.len() > current {
-            if let RefMessageFormat::Placeholder(n) = formats[current] {
-                if n >= N {
-                    return Err(CreateMessageError::InvalidNumber { number: n, n: N });
-                }
-                numbers[n] = true;
+        match check_numbers::<N>(formats, false) {
+            None => Ok(Self { formats }),
+            Some(NumberError::Invalid(number)) => {
+                Err(CreateMessageError::InvalidNumber { number, n: N })
             }
-            current += 1;
+            Some(NumberError::Without(number)) => {
+                Err(CreateMessageError::WithoutNumber { number, n: N })
+            }
+            Some(NumberError::Section) => Err(CreateMessageError::UnbalancedSection),
         }
+    }
 
-        let mut current = 0;
-
-        while numbers.len() > current {
-            if !numbers[current] {
-                return Err(CreateMessageError::WithoutNumber {
-                    number: current,
-                    n: N,
-                });
+    /// Creates a message like [`RefMessage::new`], but allows some arguments below `N` to be unused,
+    /// as in a language that omits an argument of a message.
+    ///
+    /// The unused arguments are ignored when the message is formatted.
+    /// The number of arguments is kept by `N`, and by the number of arguments
+    /// of a [`DynMessage`](crate::DynMessage) converted from the message,
+    /// so the message is displayed as its text alone.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use local_fmt::{DynMessage, RefMessage, RefMessageFormat};
+    ///
+    /// let message = RefMessage::<1>::new_with_unused_args(&[
+    ///     RefMessageFormat::RefText("Hello!"),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(message.format(&["Rust"]), "Hello!");
+    /// assert_eq!(message.to_string(), "Hello!");
+    /// assert_eq!(DynMessage::from(message).args(), 1);
+    /// ```
+    pub const fn new_with_unused_args(
+        formats: &'a [RefMessageFormat<'a>],
+    ) -> Result<Self, CreateMessageError> {
+        match check_numbers::<N>(formats, true) {
+            None => Ok(Self { formats }),
+            Some(NumberError::Invalid(number)) => {
+                Err(CreateMessageError::InvalidNumber { number, n: N })
+            }
+            Some(NumberError::Without(number)) => {
+                Err(CreateMessageError::WithoutNumber { number, n: N })
             }
-            current += 1;
+            Some(NumberError::Section) => Err(CreateMessageError::UnbalancedSection),
         }
-
-        Ok(Self { formats })
     }
 
     #[track_caller]
     pub const fn new_panic(formats: &'a [RefMessageFormat<'a>]) -> Self {
-        match Self::new(formats) {
-            Ok(message) => message,
-            Err(error) => error.panic(),
+        // The error is built in place rather than taken from `new`, because a
+        // `CreateMessageError` returned by a call cannot be dropped in a const fn.
+        match check_numbers::<N>(formats, false) {
+            None => Self { formats },
+            Some(NumberError::Invalid(number)) => {
+                CreateMessageError::InvalidNumber { number, n: N }.panic()
+            }
+            Some(NumberError::Without(number)) => {
+                CreateMessageError::WithoutNumber { number, n: N }.panic()
+            }
+            Some(NumberError::Section) => CreateMessageError::UnbalancedSection.panic(),
         }
     }
 
     pub fn format(&self, args: &[&str; N]) -> String {
-        let mut result = String::new();
-
-        for format in self.formats {
-            match format {
-                RefMessageFormat::RefText(text) => result.push_str(text),
-                RefMessageFormat::UNumber(n) => result.push_str(&n.to_string()),
-                RefMessageFormat::INumber(n) => result.push_str(&n.to_string()),
-                RefMessageFormat::Placeholder(n) => result.push_str(args[*n]),
-            }
-        }
-
-        result
+        format_segments(self.formats.iter().cloned(), args)
     }
 
     /// Returns the number of format elements in this message.
-    /// 
+    ///
     /// This counts both text segments and placeholders.
-    /// 
+    ///
     /// # Example
     /// ```
     /// use local_fmt::{RefMessageFormat, RefMessage};
-    /// 
+    ///
     /// const MSG: RefMessage<'static, 1> = RefMessage::new_panic(&[
     ///     RefMessageFormat::RefText("Hello "),
     ///     RefMessageFormat::Placeholder(0),
@@ -110,11 +189,11 @@ impl<'a, const N: usize> RefMessage<'a, N> {
     }
 
     /// Returns true if this message has no format elements.
-    /// 
+    ///
     /// # Example
     /// ```
     /// use local_fmt::RefMessage;
-    /// 
+    ///
     /// const MSG: RefMessage<'static, 0> = RefMessage::new_panic(&[]);
     /// assert!(MSG.is_empty());
     /// ```
@@ -123,14 +202,14 @@ impl<'a, const N: usize> RefMessage<'a, N> {
     }
 
     /// Returns a reference to the internal format elements.
-    /// 
+    ///
     /// This provides access to the underlying representation of the message format,
     /// which consists of text segments, placeholders, and numeric constants.
-    /// 
+    ///
     /// # Example
     /// ```
     /// use local_fmt::{RefMessageFormat, RefMessage};
-    /// 
+    ///
     /// const MSG: RefMessage<'static, 1> = RefMessage::new_panic(&[
     ///     RefMessageFormat::RefText("Count: "),
     ///     RefMessageFormat::Placeholder(0),
@@ -142,6 +221,56 @@ impl<'a, const N: usize> RefMessage<'a, N> {
     }
 }
 
+#[derive(Clone, Copy)]
+enum NumberError {
+    Invalid(usize),
+    Without(usize),
+    Section,
+}
+
+/// Checks that the placeholders of `formats` are numbered from 0 to N-1, every number being used
+/// unless `allow_unused` is set, and that its conditional sections are balanced.
+const fn check_numbers<const N: usize>(
+    formats: &[RefMessageFormat],
+    allow_unused: bool,
+) -> Option<NumberError> {
+    let mut numbers = [false; N];
+    let mut depth = 0usize;
+
+    let mut current = 0;
+
+    while formats.len() > current {
+        if let Some(n) = formats[current].arg() {
+            if n >= N {
+                return Some(NumberError::Invalid(n));
+            }
+            numbers[n] = true;
+        }
+        match formats[current] {
+            RefMessageFormat::SectionStart(_) => depth += 1,
+            RefMessageFormat::SectionEnd if depth == 0 => return Some(NumberError::Section),
+            RefMessageFormat::SectionEnd => depth -= 1,
+            _ => {}
+        }
+        current += 1;
+    }
+
+    if depth != 0 {
+        return Some(NumberError::Section);
+    }
+
+    let mut current = 0;
+
+    while numbers.len() > current {
+        if !numbers[current] && !allow_unused {
+            return Some(NumberError::Without(current));
+        }
+        current += 1;
+    }
+
+    None
+}
+
 impl<const N: usize> StaticMessage<N> {
     /// Formats the message with the given arguments.
     ///
@@ -209,7 +338,37 @@ impl<const N: usize> StaticMessage<N> {
                 RefMessageFormat::RefText(text) => process!(text.as_bytes()),
                 RefMessageFormat::UNumber(n) => process!(const_u128_to_str(*n).buffer()),
                 RefMessageFormat::INumber(n) => process!(const_i128_to_str(*n).buffer()),
+                RefMessageFormat::Float(n) => process!(const_float_to_str(*n).buffer()),
+                RefMessageFormat::Char(c) => process!(const_char_to_str(*c).buffer()),
+                RefMessageFormat::Bool(b) => process!(if *b {
+                    b"true".as_slice()
+                } else {
+                    b"false".as_slice()
+                }),
                 RefMessageFormat::Placeholder(n) => process!(args[*n]),
+                RefMessageFormat::PlaceholderDefault(n, text) => {
+                    process!(if args[*n].is_empty() {
+                        text.as_bytes()
+                    } else {
+                        args[*n]
+                    })
+                }
+                RefMessageFormat::SectionStart(n) if args[*n].is_empty() => {
+                    // Skips the section, including the sections nested in it.
+                    let mut depth = 0;
+                    loop {
+                        match self.formats[i] {
+                            RefMessageFormat::SectionStart(_) => depth += 1,
+                            RefMessageFormat::SectionEnd => depth -= 1,
+                            _ => {}
+                        }
+                        i += 1;
+                        if depth == 0 || i == self.formats.len() {
+                            break;
+                        }
+                    }
+                }
+                RefMessageFormat::SectionStart(_) | RefMessageFormat::SectionEnd => i += 1,
             }
         }
 
@@ -219,10 +378,6 @@ impl<const N: usize> StaticMessage<N> {
 
 impl<const N: usize> Display for RefMessage<'_, N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for format in self.formats {
-            write!(f, "{}", format)?;
-        }
-
-        Ok(())
+        write_segments(f, self.formats.iter().cloned())
     }
 }
@@ -0,0 +1,216 @@
+use std::borrow::Cow;
+
+use super::{merge_texts, AllocMessage, AllocMessageFormat, RefMessage, RefMessageFormat};
+
+/// A message with `N` arguments, whether compiled into the program as a [`StaticMessage<N>`]
+/// or loaded at runtime as an [`AllocMessage<N>`].
+///
+/// A `&'static str`, a `Cow<'static, str>` or a `String` is a message without arguments.
+///
+/// [`StaticMessage<N>`]: crate::StaticMessage
+///
+/// # Example
+/// ```
+/// use std::str::FromStr;
+/// use local_fmt::{gen_static_message, AllocMessage, Message, StaticMessage};
+///
+/// fn greet(message: &impl Message<1>, user: &str) -> String {
+///     message.format(&[user])
+/// }
+///
+/// const COMPILED: StaticMessage<1> = gen_static_message!("Hello, {0}!");
+/// let loaded = AllocMessage::<1>::from_str("Welcome back, {0}.").unwrap();
+///
+/// assert_eq!(greet(&COMPILED, "Rust"), "Hello, Rust!");
+/// assert_eq!(greet(&loaded, "Rust"), "Welcome back, Rust.");
+/// assert_eq!(loaded.segments().count(), 3);
+/// ```
+pub trait Message<const N: usize> {
+    /// Returns the segments of the message, as texts, constants and placeholders.
+    fn segments(&self) -> Segments<'_>;
+
+    /// Returns the number of arguments of the message, which is `N`.
+    fn args(&self) -> usize {
+        N
+    }
+
+    /// Formats the message with the given arguments.
+    fn format(&self, args: &[&str; N]) -> String {
+        format_segments(self.segments(), args)
+    }
+}
+
+/// Formats the segments of a message with the given arguments,
+/// skipping the conditional sections whose argument is empty.
+pub(crate) fn format_segments<'a>(
+    mut segments: impl Iterator<Item = RefMessageFormat<'a>>,
+    args: &[&str],
+) -> String {
+    let mut result = String::new();
+
+    while let Some(segment) = segments.next() {
+        match segment {
+            RefMessageFormat::RefText(text) => result.push_str(text),
+            RefMessageFormat::UNumber(n) => result.push_str(&n.to_string()),
+            RefMessageFormat::INumber(n) => result.push_str(&n.to_string()),
+            RefMessageFormat::Float(n) => result.push_str(&n.to_string()),
+            RefMessageFormat::Char(c) => result.push(c),
+            RefMessageFormat::Bool(b) => result.push_str(if b { "true" } else { "false" }),
+            RefMessageFormat::Placeholder(n) => result.push_str(args[n]),
+            RefMessageFormat::PlaceholderDefault(n, text) if args[n].is_empty() => {
+                result.push_str(text)
+            }
+            RefMessageFormat::PlaceholderDefault(n, _) => result.push_str(args[n]),
+            RefMessageFormat::SectionStart(n) if args[n].is_empty() => skip_section(&mut segments),
+            RefMessageFormat::SectionStart(_) | RefMessageFormat::SectionEnd => {}
+        }
+    }
+
+    result
+}
+
+/// Skips the segments of a conditional section that was just started,
+/// up to and including its end.
+pub(crate) fn skip_section<'a>(segments: &mut impl Iterator<Item = RefMessageFormat<'a>>) {
+    let mut depth = 1usize;
+    for segment in segments {
+        match segment {
+            RefMessageFormat::SectionStart(_) => depth += 1,
+            RefMessageFormat::SectionEnd => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            break;
+        }
+    }
+}
+
+/// An iterator over the segments of a [`Message`], returned by [`Message::segments`].
+#[derive(Debug, Clone)]
+pub struct Segments<'a> {
+    inner: SegmentsInner<'a>,
+}
+
+#[derive(Debug, Clone)]
+enum SegmentsInner<'a> {
+    Ref(std::slice::Iter<'a, RefMessageFormat<'a>>),
+    Alloc(std::slice::Iter<'a, AllocMessageFormat>),
+    Text(Option<&'a str>),
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = RefMessageFormat<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            SegmentsInner::Ref(iter) => iter.next().cloned(),
+            SegmentsInner::Alloc(iter) => iter.next().map(AllocMessageFormat::as_ref_format),
+            SegmentsInner::Text(text) => text.take().map(RefMessageFormat::RefText),
+        }
+    }
+}
+
+impl<const N: usize> Message<N> for RefMessage<'_, N> {
+    fn segments(&self) -> Segments<'_> {
+        Segments {
+            inner: SegmentsInner::Ref(self.formats().iter()),
+        }
+    }
+}
+
+impl<const N: usize> Message<N> for AllocMessage<N> {
+    fn segments(&self) -> Segments<'_> {
+        Segments {
+            inner: SegmentsInner::Alloc(self.formats().iter()),
+        }
+    }
+}
+
+impl Message<0> for &'static str {
+    fn segments(&self) -> Segments<'_> {
+        Segments {
+            inner: SegmentsInner::Text(Some(*self).filter(|text| !text.is_empty())),
+        }
+    }
+}
+
+impl Message<0> for Cow<'static, str> {
+    fn segments(&self) -> Segments<'_> {
+        Segments {
+            inner: SegmentsInner::Text(Some(&**self).filter(|text| !text.is_empty())),
+        }
+    }
+}
+
+impl Message<0> for String {
+    fn segments(&self) -> Segments<'_> {
+        Segments {
+            inner: SegmentsInner::Text(Some(self.as_str()).filter(|text| !text.is_empty())),
+        }
+    }
+}
+
+/// The formats of an [`AllocMessage`] borrowed as [`RefMessageFormat`]s,
+/// returned by [`AllocMessage::as_ref_message`].
+///
+/// A [`RefMessage`] borrows a slice of [`RefMessageFormat`]s,
+/// which this holds while the message is used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefMessageBuf<'a, const N: usize> {
+    formats: Vec<RefMessageFormat<'a>>,
+}
+
+impl<const N: usize> RefMessageBuf<'_, N> {
+    /// Returns the message borrowing these formats.
+    pub fn message(&self) -> RefMessage<'_, N> {
+        // SAFETY: the formats are those of an AllocMessage<N>, whose placeholders are checked.
+        unsafe { RefMessage::new_unchecked(&self.formats) }
+    }
+}
+
+impl<const N: usize> Message<N> for RefMessageBuf<'_, N> {
+    fn segments(&self) -> Segments<'_> {
+        Segments {
+            inner: SegmentsInner::Ref(self.formats.iter()),
+        }
+    }
+}
+
+impl<const N: usize> AllocMessage<N> {
+    /// Borrows the message as a [`RefMessage`], through a [`RefMessageBuf`] holding its formats.
+    ///
+    /// # Example
+    /// ```
+    /// use std::str::FromStr;
+    /// use local_fmt::{AllocMessage, RefMessage};
+    ///
+    /// let message = AllocMessage::<1>::from_str("Hello, {0}!").unwrap();
+    /// let buf = message.as_ref_message();
+    /// let message: RefMessage<1> = buf.message();
+    ///
+    /// assert_eq!(message.format(&["Rust"]), "Hello, Rust!");
+    /// assert_eq!(message.to_alloc().to_string(), "Hello, {0}!");
+    /// ```
+    pub fn as_ref_message(&self) -> RefMessageBuf<'_, N> {
+        RefMessageBuf {
+            formats: self.segments().collect(),
+        }
+    }
+}
+
+impl<const N: usize> RefMessage<'_, N> {
+    /// Copies the message into an [`AllocMessage`], rendering its constants such as `{u:MAX}`
+    /// as text, so that it formats to the same strings.
+    ///
+    /// A message created by [`RefMessage::new_with_unused_args`] gives an [`AllocMessage`]
+    /// that leaves the same arguments unused, like [`AllocMessage::new_with_unused_args`].
+    pub fn to_alloc(&self) -> AllocMessage<N> {
+        let format = self
+            .formats()
+            .iter()
+            .map(RefMessageFormat::to_alloc_format)
+            .collect();
+        // SAFETY: the placeholders of a RefMessage<N> are below N and its sections are balanced.
+        unsafe { AllocMessage::new_unchecked(merge_texts(format)) }
+    }
+}
@@ -1,3 +1,6 @@
+mod float;
+pub use float::*;
+
 /// A wrapper for a byte array buffer.
 /// The total field is the number of bytes used in the buffer.
 /// The buffer field is the byte array.
@@ -19,17 +22,16 @@ impl<const N: usize> UtilBufWrapper<N> {
     }
 
     /// Returns the buffer as a str.
-    /// 
+    ///
     /// # Safety
     /// This function assumes that the buffer contains valid UTF-8 bytes.
     /// This is safe because:
-    /// 1. The buffer is initialized with ASCII digits and '-' character only
-    /// 2. All operations that modify the buffer (const_u128_to_str, const_i128_to_str)
-    ///    only write ASCII characters (0-9, '-')
-    /// 3. ASCII is a subset of UTF-8, so ASCII bytes are always valid UTF-8
+    /// 1. The number functions (const_u128_to_str, const_i128_to_str, const_float_to_str)
+    ///    only write ASCII characters, such as digits, '-' and '.'
+    /// 2. const_char_to_str writes the UTF-8 encoding of a char
+    /// 3. const_format only writes whole str slices and the outputs above
     pub const fn as_str(&self) -> &str {
-        // SAFETY: Buffer only contains ASCII digits and '-' character,
-        // which are always valid UTF-8
+        // SAFETY: Buffer only contains whole UTF-8 sequences
         unsafe { std::str::from_utf8_unchecked(self.buffer()) }
     }
 }
@@ -100,6 +102,42 @@ pub const fn const_i128_to_str(n: i128) -> UtilBufWrapper<40> {
     UtilBufWrapper::new(buffer, i)
 }
 
+/// Converts a char to a str as a byte array, encoded as UTF-8.
+/// The buffer is 4 bytes long.
+/// The maximum length of a char in UTF-8 is 4 bytes.
+///
+/// # Example
+/// ```
+/// use local_fmt::utils::const_char_to_str;
+///
+/// const BUFFER: &[u8] = const_char_to_str('→').buffer();
+/// assert_eq!(BUFFER, "→".as_bytes());
+/// ```
+pub const fn const_char_to_str(c: char) -> UtilBufWrapper<4> {
+    let code = c as u32;
+    let mut buffer = [0u8; 4];
+    let total = if code < 0x80 {
+        buffer[0] = code as u8;
+        1
+    } else if code < 0x800 {
+        buffer[0] = 0xC0 | (code >> 6) as u8;
+        buffer[1] = 0x80 | (code & 0x3F) as u8;
+        2
+    } else if code < 0x10000 {
+        buffer[0] = 0xE0 | (code >> 12) as u8;
+        buffer[1] = 0x80 | ((code >> 6) & 0x3F) as u8;
+        buffer[2] = 0x80 | (code & 0x3F) as u8;
+        3
+    } else {
+        buffer[0] = 0xF0 | (code >> 18) as u8;
+        buffer[1] = 0x80 | ((code >> 12) & 0x3F) as u8;
+        buffer[2] = 0x80 | ((code >> 6) & 0x3F) as u8;
+        buffer[3] = 0x80 | (code & 0x3F) as u8;
+        4
+    };
+    UtilBufWrapper::new(buffer, total)
+}
+
 /// A macro for creating a panic message with placeholders for arguments.
 /// The message is formatted with the arguments and then a panic is raised.
 ///
@@ -0,0 +1,554 @@
+use std::fmt::Display;
+
+use super::UtilBufWrapper;
+
+/// The maximum length of a float rendered by [`const_float_to_str`]:
+/// a sign, the 309 integer digits of `f64::MAX`, a point and 255 fractional digits.
+pub const FLOAT_STR_LEN: usize = 566;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// The types a [`ConstFloat`] can be created from, `f32` and `f64`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a floating-point type",
+    label = "expected `f32` or `f64`"
+)]
+pub trait ConstFloatValue: Copy + sealed::Sealed {
+    #[doc(hidden)]
+    const SINGLE: bool;
+}
+
+impl ConstFloatValue for f32 {
+    const SINGLE: bool = true;
+}
+
+impl ConstFloatValue for f64 {
+    const SINGLE: bool = false;
+}
+
+/// A floating-point number with an optional precision, which can be rendered in const contexts.
+///
+/// It is rendered like the [`Display`] implementation of its `f32` or `f64`,
+/// as with `{}` without a precision, and as with `{:.precision}` otherwise.
+///
+/// # Example
+/// ```
+/// use local_fmt::{const_float_to_str, ConstFloat, UtilBufWrapper, FLOAT_STR_LEN};
+///
+/// const RATIO: ConstFloat = ConstFloat::new(0.125f64, Some(2));
+/// const VERSION: ConstFloat = ConstFloat::new(1.5f32, None);
+///
+/// const TEXT: UtilBufWrapper<FLOAT_STR_LEN> = const_float_to_str(RATIO);
+/// assert_eq!(TEXT.as_str(), "0.12");
+/// assert_eq!(RATIO.to_string(), "0.12");
+/// assert_eq!(VERSION.to_string(), "1.5");
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ConstFloat {
+    bits: u64,
+    single: bool,
+    precision: Option<u8>,
+}
+
+impl ConstFloat {
+    pub const fn new<T: ConstFloatValue>(value: T, precision: Option<u8>) -> Self {
+        // SAFETY: ConstFloatValue is sealed, and `T::SINGLE` tells whether `T` is `f32` or `f64`,
+        // whose bits are `u32` and `u64`.
+        let bits = unsafe {
+            if T::SINGLE {
+                std::mem::transmute_copy::<T, u32>(&value) as u64
+            } else {
+                std::mem::transmute_copy::<T, u64>(&value)
+            }
+        };
+        Self {
+            bits,
+            single: T::SINGLE,
+            precision,
+        }
+    }
+
+    /// Returns the number of digits after the decimal point, if any.
+    pub const fn precision(&self) -> Option<u8> {
+        self.precision
+    }
+
+    /// Returns the value as an `f64`, which represents every `f32` exactly.
+    pub fn to_f64(&self) -> f64 {
+        if self.single {
+            f32::from_bits(self.bits as u32) as f64
+        } else {
+            f64::from_bits(self.bits)
+        }
+    }
+}
+
+impl Display for ConstFloat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.single, self.precision) {
+            (true, None) => write!(f, "{}", f32::from_bits(self.bits as u32)),
+            (true, Some(precision)) => {
+                write!(
+                    f,
+                    "{:.*}",
+                    precision as usize,
+                    f32::from_bits(self.bits as u32)
+                )
+            }
+            (false, None) => write!(f, "{}", f64::from_bits(self.bits)),
+            (false, Some(precision)) => {
+                write!(f, "{:.*}", precision as usize, f64::from_bits(self.bits))
+            }
+        }
+    }
+}
+
+/// Renders a float exactly like its [`Display`] implementation, in const contexts.
+///
+/// Without a precision, the shortest digits that read back as the same float are rendered,
+/// and with a precision, the exact value is rounded half to even.
+///
+/// # Example
+/// ```
+/// use local_fmt::{const_float_to_str, ConstFloat};
+///
+/// assert_eq!(const_float_to_str(ConstFloat::new(0.1f32, None)).as_str(), "0.1");
+/// assert_eq!(const_float_to_str(ConstFloat::new(1e21f64, None)).as_str(), "1000000000000000000000");
+/// assert_eq!(const_float_to_str(ConstFloat::new(-2.5f64, Some(0))).as_str(), "-2");
+/// ```
+pub const fn const_float_to_str(float: ConstFloat) -> UtilBufWrapper<FLOAT_STR_LEN> {
+    let decoded = decode(float.bits, float.single);
+    let mut out = Writer::new();
+    if decoded.negative && !matches!(decoded.kind, Kind::Nan) {
+        out = out.push(b'-');
+    }
+    let out = match decoded.kind {
+        Kind::Nan => out.push_bytes(b"NaN"),
+        Kind::Infinite => out.push_bytes(b"inf"),
+        Kind::Zero => match float.precision {
+            None | Some(0) => out.push(b'0'),
+            Some(precision) => out.push_bytes(b"0.").push_zeros(precision as usize),
+        },
+        Kind::Finite => match float.precision {
+            None => shortest(out, decoded),
+            Some(precision) => fixed(out, decoded, precision as usize),
+        },
+    };
+    UtilBufWrapper::new(out.buffer, out.len)
+}
+
+enum Kind {
+    Nan,
+    Infinite,
+    Zero,
+    Finite,
+}
+
+/// A float decoded as `mant * 2^exp`, like `integer_decode` of the standard library.
+struct Decoded {
+    negative: bool,
+    kind: Kind,
+    mant: u64,
+    exp: i32,
+    /// The neighbors are `mant - 2` and `mant + 2`, for subnormal floats whose `mant` is doubled.
+    subnormal: bool,
+    /// The lower neighbor is closer than the upper one, for normal floats without fraction bits.
+    asymmetric: bool,
+}
+
+const fn decode(bits: u64, single: bool) -> Decoded {
+    let (negative, exp_bits, frac, frac_len, exp_max, bias) = if single {
+        (
+            (bits >> 31) & 1 == 1,
+            (bits >> 23) & 0xff,
+            bits & 0x7f_ffff,
+            23,
+            0xff,
+            150,
+        )
+    } else {
+        (
+            bits >> 63 == 1,
+            (bits >> 52) & 0x7ff,
+            bits & 0xf_ffff_ffff_ffff,
+            52,
+            0x7ff,
+            1075,
+        )
+    };
+    let kind = if exp_bits == exp_max {
+        if frac == 0 {
+            Kind::Infinite
+        } else {
+            Kind::Nan
+        }
+    } else if exp_bits == 0 && frac == 0 {
+        Kind::Zero
+    } else {
+        Kind::Finite
+    };
+    let (mant, exp) = if exp_bits == 0 {
+        (frac << 1, -bias)
+    } else {
+        (frac | (1 << frac_len), exp_bits as i32 - bias)
+    };
+    Decoded {
+        negative,
+        kind,
+        mant,
+        exp,
+        subnormal: exp_bits == 0,
+        asymmetric: exp_bits != 0 && frac == 0,
+    }
+}
+
+/// Renders the shortest digits within the rounding interval of the float,
+/// following the Dragon4 algorithm of `core::num::flt2dec`.
+const fn shortest(out: Writer, decoded: Decoded) -> Writer {
+    let inclusive = decoded.mant & 1 == 0;
+    let (mant, minus, plus, exp) = if decoded.subnormal {
+        (decoded.mant, 1, 1, decoded.exp)
+    } else if decoded.asymmetric {
+        (decoded.mant << 2, 1, 2, decoded.exp - 2)
+    } else {
+        (decoded.mant << 1, 1, 1, decoded.exp - 1)
+    };
+
+    let mut k = estimate_scaling_factor(mant + plus, exp);
+    let mut mant = Big::from_u64(mant);
+    let mut minus = Big::from_u64(minus);
+    let mut plus = Big::from_u64(plus);
+    let mut scale = Big::from_u64(1);
+    if exp < 0 {
+        scale = scale.mul_pow2(-exp as usize);
+    } else {
+        mant = mant.mul_pow2(exp as usize);
+        minus = minus.mul_pow2(exp as usize);
+        plus = plus.mul_pow2(exp as usize);
+    }
+    if k >= 0 {
+        scale = scale.mul_pow10(k as usize);
+    } else {
+        mant = mant.mul_pow10(-k as usize);
+        minus = minus.mul_pow10(-k as usize);
+        plus = plus.mul_pow10(-k as usize);
+    }
+    if below(scale, mant.add(plus), inclusive) {
+        k += 1;
+    } else {
+        mant = mant.mul_small(10);
+        minus = minus.mul_small(10);
+        plus = plus.mul_small(10);
+    }
+
+    let mut digits = [0u8; DIGITS];
+    let mut len = 0;
+    let (down, up) = loop {
+        let mut digit = 0;
+        while mant.cmp(scale) >= 0 {
+            mant = mant.sub(scale);
+            digit += 1;
+        }
+        digits[len] = b'0' + digit;
+        len += 1;
+
+        let down = below(mant, minus, inclusive);
+        let up = below(scale, mant.add(plus), inclusive);
+        if down || up {
+            break (down, up);
+        }
+        mant = mant.mul_small(10);
+        minus = minus.mul_small(10);
+        plus = plus.mul_small(10);
+    };
+
+    if up && (!down || mant.mul_pow2(1).cmp(scale) >= 0) {
+        let mut i = len;
+        while i > 0 && digits[i - 1] == b'9' {
+            i -= 1;
+            digits[i] = b'0';
+        }
+        if i > 0 {
+            digits[i - 1] += 1;
+        } else {
+            // The digits were all nines, so the rounded number has one more digit.
+            digits[0] = b'1';
+            digits[len] = b'0';
+            len += 1;
+            k += 1;
+        }
+    }
+
+    let point = k;
+    if point <= 0 {
+        out.push_bytes(b"0.")
+            .push_zeros(-point as usize)
+            .push_digits(&digits, 0, len)
+    } else if (point as usize) < len {
+        out.push_digits(&digits, 0, point as usize)
+            .push(b'.')
+            .push_digits(&digits, point as usize, len)
+    } else {
+        out.push_digits(&digits, 0, len)
+            .push_zeros(point as usize - len)
+    }
+}
+
+/// Renders the float rounded half to even to `precision` digits after the decimal point.
+const fn fixed(out: Writer, decoded: Decoded, precision: usize) -> Writer {
+    let mant = Big::from_u64(decoded.mant);
+    if decoded.exp >= 0 {
+        let (digits, len) = mant.mul_pow2(decoded.exp as usize).to_decimal();
+        let out = out.push_digits(&digits, 0, len);
+        return if precision == 0 {
+            out
+        } else {
+            out.push(b'.').push_zeros(precision)
+        };
+    }
+
+    let shift = -decoded.exp as usize;
+    let scaled = mant.mul_pow10(precision);
+    let mut rounded = scaled.shr(shift);
+    let rem = scaled.sub(rounded.mul_pow2(shift));
+    let half = Big::from_u64(1).mul_pow2(shift - 1);
+    let order = rem.cmp(half);
+    if order > 0 || (order == 0 && rounded.limbs[0] & 1 == 1) {
+        rounded = rounded.add(Big::from_u64(1));
+    }
+
+    let (digits, len) = rounded.to_decimal();
+    if len <= precision {
+        let out = out.push(b'0');
+        if precision == 0 {
+            return out;
+        }
+        out.push(b'.')
+            .push_zeros(precision - len)
+            .push_digits(&digits, 0, len)
+    } else if precision == 0 {
+        out.push_digits(&digits, 0, len)
+    } else {
+        out.push_digits(&digits, 0, len - precision)
+            .push(b'.')
+            .push_digits(&digits, len - precision, len)
+    }
+}
+
+/// Estimates `k` such that `10^(k-1) <= mant * 2^exp < 10^(k+1)`.
+const fn estimate_scaling_factor(mant: u64, exp: i32) -> i32 {
+    let nbits = 64 - (mant - 1).leading_zeros() as i64;
+    (((nbits + exp as i64) * 1292913986) >> 32) as i32
+}
+
+/// Returns `a < b`, or `a <= b` if the rounding interval is inclusive.
+const fn below(a: Big, b: Big, inclusive: bool) -> bool {
+    let order = a.cmp(b);
+    order < 0 || (inclusive && order == 0)
+}
+
+/// The number of decimal digits of the largest integer [`Big`] holds in practice, `f64::MAX`.
+const DIGITS: usize = 320;
+
+const LIMBS: usize = 40;
+
+/// An unsigned integer of 1280 bits, which holds the scaled floats of [`shortest`] and [`fixed`].
+#[derive(Clone, Copy)]
+struct Big {
+    limbs: [u32; LIMBS],
+}
+
+impl Big {
+    const fn from_u64(n: u64) -> Self {
+        let mut limbs = [0; LIMBS];
+        limbs[0] = n as u32;
+        limbs[1] = (n >> 32) as u32;
+        Self { limbs }
+    }
+
+    const fn is_zero(self) -> bool {
+        let mut i = 0;
+        while i < LIMBS {
+            if self.limbs[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns -1, 0 or 1 as `self` is less than, equal to or greater than `other`.
+    const fn cmp(self, other: Self) -> i8 {
+        let mut i = LIMBS;
+        while i > 0 {
+            i -= 1;
+            if self.limbs[i] != other.limbs[i] {
+                return if self.limbs[i] < other.limbs[i] {
+                    -1
+                } else {
+                    1
+                };
+            }
+        }
+        0
+    }
+
+    const fn add(mut self, other: Self) -> Self {
+        let mut carry = 0u64;
+        let mut i = 0;
+        while i < LIMBS {
+            let sum = self.limbs[i] as u64 + other.limbs[i] as u64 + carry;
+            self.limbs[i] = sum as u32;
+            carry = sum >> 32;
+            i += 1;
+        }
+        self
+    }
+
+    /// Subtracts `other`, which must not be greater than `self`.
+    const fn sub(mut self, other: Self) -> Self {
+        let mut borrow = 0u64;
+        let mut i = 0;
+        while i < LIMBS {
+            let diff = (self.limbs[i] as u64)
+                .wrapping_sub(other.limbs[i] as u64)
+                .wrapping_sub(borrow);
+            self.limbs[i] = diff as u32;
+            borrow = (diff >> 63) & 1;
+            i += 1;
+        }
+        self
+    }
+
+    const fn mul_small(mut self, n: u32) -> Self {
+        let mut carry = 0u64;
+        let mut i = 0;
+        while i < LIMBS {
+            let product = self.limbs[i] as u64 * n as u64 + carry;
+            self.limbs[i] = product as u32;
+            carry = product >> 32;
+            i += 1;
+        }
+        self
+    }
+
+    const fn mul_pow2(self, bits: usize) -> Self {
+        let (words, bits) = (bits / 32, bits % 32);
+        let mut limbs = [0; LIMBS];
+        let mut i = LIMBS;
+        while i > words {
+            i -= 1;
+            let mut limb = self.limbs[i - words] << bits;
+            if bits > 0 && i > words {
+                limb |= self.limbs[i - words - 1] >> (32 - bits);
+            }
+            limbs[i] = limb;
+        }
+        Self { limbs }
+    }
+
+    const fn shr(self, bits: usize) -> Self {
+        let (words, bits) = (bits / 32, bits % 32);
+        let mut limbs = [0; LIMBS];
+        let mut i = 0;
+        while i + words < LIMBS {
+            let mut limb = self.limbs[i + words] >> bits;
+            if bits > 0 && i + words + 1 < LIMBS {
+                limb |= self.limbs[i + words + 1] << (32 - bits);
+            }
+            limbs[i] = limb;
+            i += 1;
+        }
+        Self { limbs }
+    }
+
+    const fn mul_pow10(mut self, mut n: usize) -> Self {
+        while n >= 9 {
+            self = self.mul_small(1_000_000_000);
+            n -= 9;
+        }
+        self.mul_small(10u32.pow(n as u32))
+    }
+
+    const fn div_rem_small(mut self, n: u32) -> (Self, u32) {
+        let mut rem = 0u64;
+        let mut i = LIMBS;
+        while i > 0 {
+            i -= 1;
+            let value = (rem << 32) | self.limbs[i] as u64;
+            self.limbs[i] = (value / n as u64) as u32;
+            rem = value % n as u64;
+        }
+        (self, rem as u32)
+    }
+
+    /// Returns the decimal digits, most significant first, and their number.
+    const fn to_decimal(mut self) -> ([u8; DIGITS], usize) {
+        let mut reversed = [0u8; DIGITS];
+        let mut len = 0;
+        loop {
+            let (quotient, rem) = self.div_rem_small(10);
+            reversed[len] = b'0' + rem as u8;
+            len += 1;
+            self = quotient;
+            if self.is_zero() {
+                break;
+            }
+        }
+
+        let mut digits = [0u8; DIGITS];
+        let mut i = 0;
+        while i < len {
+            digits[i] = reversed[len - 1 - i];
+            i += 1;
+        }
+        (digits, len)
+    }
+}
+
+struct Writer {
+    buffer: [u8; FLOAT_STR_LEN],
+    len: usize,
+}
+
+impl Writer {
+    const fn new() -> Self {
+        Self {
+            buffer: [0; FLOAT_STR_LEN],
+            len: 0,
+        }
+    }
+
+    const fn push(mut self, byte: u8) -> Self {
+        self.buffer[self.len] = byte;
+        self.len += 1;
+        self
+    }
+
+    const fn push_bytes(self, bytes: &[u8]) -> Self {
+        self.push_digits(bytes, 0, bytes.len())
+    }
+
+    const fn push_digits(mut self, digits: &[u8], from: usize, to: usize) -> Self {
+        let mut i = from;
+        while i < to {
+            self = self.push(digits[i]);
+            i += 1;
+        }
+        self
+    }
+
+    const fn push_zeros(mut self, n: usize) -> Self {
+        let mut i = 0;
+        while i < n {
+            self = self.push(b'0');
+            i += 1;
+        }
+        self
+    }
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct HelpMessages {
+    pub usage: StaticMessage<2>,
+    pub version: StaticMessage<2>,
+    pub footer: StaticMessage<0>,
+}
+
+struct Messages {
+    pub hello: StaticMessage<1>,
+    pub title: &'static str,
+    /// A key starting with `_` that is not reserved.
+    pub _legacy: &'static str,
+    pub letters: StaticMessage<13>,
+    pub help: HelpMessages,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages { help: HelpMessages },
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/accessors",
+    accessors = EN,
+);
+
+#[test]
+fn accessors() {
+    assert_eq!(MESSAGES.hello("Rust"), "Hello, Rust!");
+    assert_eq!(MESSAGES.title().to_string(), "Home");
+    assert_eq!(MESSAGES._legacy, "Old home");
+    assert_eq!(MESSAGES.help.usage("run", "-v"), "Usage: app run -v");
+    assert_eq!(MESSAGES.help.version("app", 2), "app version 2");
+    assert_eq!(
+        MESSAGES.help.footer().to_string(),
+        "See the manual for details."
+    );
+    // More arguments than `local_format!` takes.
+    assert_eq!(
+        MESSAGES.letters('a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm'),
+        "abcdefghijklm"
+    );
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(MESSAGES.hello("Rust"), "こんにちは、Rust！");
+    assert_eq!(MESSAGES.title().to_string(), "ホーム");
+    assert_eq!(MESSAGES.help.version("app", 2), "app バージョン 2");
+    assert_eq!(
+        MESSAGES.help.footer().to_string(),
+        "詳しくはマニュアルを参照してください。"
+    );
+}
@@ -1,5 +1,11 @@
 #[test]
 fn ui() {
     let t = trybuild::TestCases::new();
-    t.compile_fail("tests/ui/**/*.rs");
+    t.compile_fail("tests/ui/gen_static_message/*.rs");
+    t.compile_fail("tests/ui/local_format/*.rs");
+    t.compile_fail("tests/ui/static_message_new_panic/*.rs");
+    // The files of def_local_fmt are found from the crate that trybuild generates
+    // in `target/tests/trybuild/local-fmt`, through `../../../../local-fmt/tests/ui`.
+    #[cfg(all(feature = "macros-toml", feature = "macros-json"))]
+    t.compile_fail("tests/ui/def_local_fmt/*.rs");
 }
@@ -0,0 +1,82 @@
+use local_fmt::{const_float_to_str, ConstFloat, ConstFloatValue, UtilBufWrapper, FLOAT_STR_LEN};
+
+fn assert_display<T: ConstFloatValue + std::fmt::Display>(value: T) {
+    for precision in [
+        None,
+        Some(0),
+        Some(1),
+        Some(2),
+        Some(3),
+        Some(7),
+        Some(17),
+        Some(30),
+    ] {
+        let float = ConstFloat::new(value, precision);
+        let expected = match precision {
+            Some(precision) => format!("{:.*}", precision as usize, value),
+            None => format!("{}", value),
+        };
+        assert_eq!(const_float_to_str(float).as_str(), expected, "{:?}", float);
+        assert_eq!(float.to_string(), expected);
+    }
+}
+
+#[test]
+fn special_values() {
+    for value in [
+        0.0,
+        -0.0,
+        1.0,
+        -1.0,
+        0.1,
+        0.5,
+        1.5,
+        2.5,
+        -0.001,
+        123.456,
+        1e21,
+        1e23,
+        1e-7,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        f64::EPSILON,
+        5e-324,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+    ] {
+        assert_display(value);
+    }
+    for value in [
+        0.1f32,
+        1.1,
+        -3.75,
+        16777216.0,
+        f32::MAX,
+        f32::MIN_POSITIVE,
+        1e-45,
+        f32::INFINITY,
+        f32::NAN,
+    ] {
+        assert_display(value);
+    }
+}
+
+#[test]
+fn pseudo_random_values() {
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    for _ in 0..2000 {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        assert_display(f64::from_bits(state));
+        assert_display(f32::from_bits((state >> 32) as u32));
+    }
+}
+
+#[test]
+fn const_context() {
+    const RATIO: ConstFloat = ConstFloat::new(2.0f64 / 3.0, Some(3));
+    const TEXT: UtilBufWrapper<FLOAT_STR_LEN> = const_float_to_str(RATIO);
+    assert_eq!(TEXT.as_str(), "0.667");
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Messages {
+    pub limit: &'static str,
+    pub offset: &'static str,
+    pub about: &'static str,
+    pub welcome: StaticMessage<1>,
+    pub ratio: &'static str,
+    pub settings: &'static str,
+}
+
+const MAX_MEMBERS: u32 = 100;
+const UTC_OFFSET: i8 = -5;
+const PRODUCT: &str = "Rustacean Chat";
+const COMPRESSION: f32 = 2.25;
+const ARROW: char = '→';
+const SYNC: bool = true;
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages,
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/consts",
+);
+
+#[test]
+fn consts() {
+    assert_eq!(MESSAGES.limit, "You can invite up to 100 members.");
+    assert_eq!(MESSAGES.offset, "The time zone is UTC-5.");
+    assert_eq!(MESSAGES.about, "Rustacean Chat is a chat app.");
+    assert_eq!(
+        MESSAGES.welcome.format(&["Ferris"]),
+        "Welcome to Rustacean Chat, Ferris!"
+    );
+    assert_eq!(MESSAGES.ratio, "Messages are stored at 2.2x compression.");
+    assert_eq!(MESSAGES.settings, "Settings → Sync: true");
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(MESSAGES.limit, "最大100人まで招待できます。");
+    assert_eq!(MESSAGES.offset, "タイムゾーンは UTC-5 です。");
+    assert_eq!(MESSAGES.about, "Rustacean Chatはチャットアプリです。");
+    assert_eq!(
+        MESSAGES.welcome.format(&["Ferris"]),
+        "Ferrisさん、Rustacean Chatへようこそ！"
+    );
+    assert_eq!(MESSAGES.ratio, "メッセージは2.2倍に圧縮されます。");
+    assert_eq!(MESSAGES.settings, "設定 → 同期: true");
+}
@@ -0,0 +1,75 @@
+use std::str::FromStr;
+
+use local_fmt::{
+    AllocMessage, ArgCountError, CreateMessageError, DynMessage, RefMessage, RefMessageFormat,
+    StaticMessage,
+};
+
+#[test]
+fn runtime_arity() {
+    let message = DynMessage::from_str("{1} and {0}").unwrap();
+    assert_eq!(message.args(), 2);
+    assert_eq!(message.format(&["a", "b"]).unwrap(), "b and a");
+    assert_eq!(
+        message.format(&["a", "b", "c"]),
+        Err(ArgCountError {
+            expected: 2,
+            given: 3
+        })
+    );
+
+    let message = DynMessage::from_str("No arguments").unwrap();
+    assert_eq!(message.args(), 0);
+    assert_eq!(message.format(&[]).unwrap(), "No arguments");
+
+    let error = DynMessage::from_str("{2} {0}").unwrap_err();
+    assert_eq!(error, CreateMessageError::WithoutNumber { number: 1, n: 3 });
+}
+
+#[test]
+fn alloc_conversions() {
+    let message = DynMessage::from_str("Hello, {0}!").unwrap();
+
+    let alloc = AllocMessage::<1>::try_from(message.clone()).unwrap();
+    assert_eq!(alloc.format(&["Rust"]), "Hello, Rust!");
+    assert_eq!(DynMessage::from(alloc), message);
+
+    assert_eq!(
+        AllocMessage::<0>::try_from(message.clone()),
+        Err(CreateMessageError::InvalidNumber { number: 0, n: 0 })
+    );
+    assert_eq!(
+        AllocMessage::<2>::try_from(message),
+        Err(CreateMessageError::WithoutNumber { number: 1, n: 2 })
+    );
+}
+
+#[test]
+fn ref_conversions() {
+    const MESSAGE: StaticMessage<1> = StaticMessage::new_panic(&[
+        RefMessageFormat::RefText("Version "),
+        RefMessageFormat::UNumber(2),
+        RefMessageFormat::RefText(" of "),
+        RefMessageFormat::Placeholder(0),
+    ]);
+
+    let message = DynMessage::from(MESSAGE);
+    assert_eq!(message.args(), 1);
+    assert_eq!(message.len(), 2);
+    assert_eq!(message.to_string(), "Version 2 of {0}");
+
+    let formats = message.ref_formats();
+    let message = RefMessage::<1>::new(&formats).unwrap();
+    assert_eq!(message.format(&["Rust"]), "Version 2 of Rust");
+    assert_eq!(
+        RefMessage::<0>::new(&formats).unwrap_err(),
+        CreateMessageError::InvalidNumber { number: 0, n: 0 }
+    );
+}
+
+#[test]
+fn display_round_trip() {
+    let message = DynMessage::from_str("{{{0}}} \\\\ {1}").unwrap();
+    let display = message.to_string();
+    assert_eq!(DynMessage::from_str(&display).unwrap(), message);
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::{borrow::Cow, sync::RwLock};
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, AllocMessage, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+const MAX_MEMBERS: u32 = 100;
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+struct ConstMessages {
+    pub title: StaticMessage<0>,
+    pub limit: StaticMessage<0>,
+    pub greeting: StaticMessage<1>,
+    pub members: StaticMessage<1>,
+    pub max_members: &'static str,
+}
+
+def_local_fmt!(
+    name = CONST_MESSAGES,
+    lang = Lang,
+    message = ConstMessages,
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/field_types",
+);
+
+struct CowMessages {
+    pub title: Cow<'static, str>,
+    pub limit: Cow<'static, str>,
+    pub greeting: StaticMessage<1>,
+    pub members: StaticMessage<1>,
+    pub max_members: u32,
+}
+
+def_local_fmt!(
+    name = COW_MESSAGES,
+    lang = Lang,
+    message = CowMessages,
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/field_types",
+);
+
+struct AllocMessages {
+    pub title: String,
+    pub limit: AllocMessage<0>,
+    pub greeting: AllocMessage<1>,
+    pub members: StaticMessage<1>,
+    pub max_members: u32,
+}
+
+def_local_fmt!(
+    name = static ALLOC_MESSAGES,
+    lang = Lang,
+    message = AllocMessages,
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/field_types",
+);
+
+#[test]
+fn field_types() {
+    assert_eq!(CONST_MESSAGES.title.format(&[]), "Rustacean Chat");
+    assert_eq!(
+        CONST_MESSAGES.limit.format(&[]),
+        "You can invite up to 100 members."
+    );
+    assert_eq!(
+        CONST_MESSAGES.greeting.format(&["Ferris"]),
+        "Hello, Ferris!"
+    );
+    assert_eq!(CONST_MESSAGES.members.format(&["3"]), "3 members");
+    assert_eq!(CONST_MESSAGES.max_members, "100");
+
+    assert_eq!(COW_MESSAGES.title, Cow::Borrowed("Rustacean Chat"));
+    assert_eq!(COW_MESSAGES.limit, "You can invite up to 100 members.");
+    assert_eq!(COW_MESSAGES.greeting.format(&["Ferris"]), "Hello, Ferris!");
+    assert_eq!(COW_MESSAGES.members.format(&["3"]), "3 members");
+    assert_eq!(COW_MESSAGES.max_members, 100);
+
+    assert_eq!(ALLOC_MESSAGES.title, "Rustacean Chat");
+    assert_eq!(
+        ALLOC_MESSAGES.limit.format(&[]),
+        "You can invite up to 100 members."
+    );
+    assert_eq!(
+        ALLOC_MESSAGES.greeting.format(&["Ferris"]),
+        "Hello, Ferris!"
+    );
+    assert_eq!(ALLOC_MESSAGES.members.format(&["3"]), "3 members");
+    assert_eq!(ALLOC_MESSAGES.max_members, 100);
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(CONST_MESSAGES.title.format(&[]), "Rustaceanチャット");
+    assert_eq!(COW_MESSAGES.limit, "最大100人まで招待できます。");
+    assert_eq!(ALLOC_MESSAGES.title, "Rustaceanチャット");
+    assert_eq!(
+        ALLOC_MESSAGES.greeting.format(&["Ferris"]),
+        "こんにちは、Ferrisさん！"
+    );
+    assert_eq!(ALLOC_MESSAGES.max_members, 50);
+}
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use local_fmt::AllocMessage;
+use local_fmt::{AllocMessage, CreateMessageError, ParseErrorKind};
 
 #[test]
 fn normal() {
@@ -16,10 +16,28 @@ fn failed() {
     let message = AllocMessage::<1>::from_str(text).unwrap_err();
     assert_eq!(
         message,
-        local_fmt::CreateMessageError::WithoutNumber { number: 0, n: 1 }
+        CreateMessageError::InvalidNumber { number: 1, n: 1 }
     );
 }
 
+#[test]
+fn too_many_placeholders() {
+    let error = AllocMessage::<1>::from_str("{0} {1}").unwrap_err();
+    assert_eq!(error, CreateMessageError::InvalidNumber { number: 1, n: 1 });
+}
+
+#[test]
+fn missing_placeholders() {
+    let error = AllocMessage::<2>::from_str("plain").unwrap_err();
+    assert_eq!(error, CreateMessageError::WithoutNumber { number: 0, n: 2 });
+
+    let error = AllocMessage::<2>::from_str("{0}").unwrap_err();
+    assert_eq!(error, CreateMessageError::WithoutNumber { number: 1, n: 2 });
+
+    let message = AllocMessage::<0>::from_str("plain").unwrap();
+    assert_eq!(message.format(&[]), "plain");
+}
+
 #[test]
 fn with_backslash() {
     let text = "Hey \\{1} {1} {0}";
@@ -27,3 +45,113 @@ fn with_backslash() {
     let text = message.format(&["World!", "Rust!"]);
     assert_eq!(text, "Hey {1} Rust! World!");
 }
+
+#[test]
+fn with_double_braces() {
+    let text = "{{{0}}} uses {{ and }}, and } alone";
+    let message = AllocMessage::<1>::from_str(text).unwrap();
+    let text = message.format(&["name"]);
+    assert_eq!(text, "{name} uses { and }, and } alone");
+}
+
+#[test]
+fn with_escaped_backslash() {
+    let text = "C:\\\\{0}\\\\ \\} \\n";
+    let message = AllocMessage::<1>::from_str(text).unwrap();
+    let text = message.format(&["Users"]);
+    assert_eq!(text, "C:\\Users\\ } \\n");
+}
+
+#[test]
+fn display_round_trip() {
+    for text in [
+        "Hello {0}",
+        "{{{0}}}",
+        "\\{0} {0}",
+        "} {0} {{",
+        "C:\\\\{0}\\\\",
+        "\\ {0} \\n \\",
+    ] {
+        let message = AllocMessage::<1>::from_str(text).unwrap();
+        let display = message.to_string();
+        assert_eq!(
+            AllocMessage::<1>::from_str(&display).unwrap(),
+            message,
+            "{}",
+            display
+        );
+    }
+}
+
+#[test]
+fn unexpected_char() {
+    let text = "こんにちは {0名前}";
+    let error = AllocMessage::<1>::from_str(text).unwrap_err();
+    assert_eq!(
+        error,
+        CreateMessageError::Parse {
+            offset: 18,
+            snippet: "{0名前}".to_string(),
+            kind: ParseErrorKind::UnexpectedChar('名'),
+        }
+    );
+    assert_eq!(&text[18..21], "名");
+}
+
+#[test]
+fn first_error() {
+    let text = "Hello {} {99999999999999999999999}";
+    let error = AllocMessage::<1>::from_str(text).unwrap_err();
+    assert_eq!(
+        error,
+        CreateMessageError::Parse {
+            offset: 6,
+            snippet: "{}".to_string(),
+            kind: ParseErrorKind::EmptyPlaceholder,
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "Invalid message syntax at byte 6: the placeholder has no number in `{}`."
+    );
+}
+
+#[test]
+fn all_errors() {
+    let text = "{99999999999999999999999} {{ok}} {0 {1";
+    let errors = AllocMessage::<2>::from_str_all(text).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            CreateMessageError::Parse {
+                offset: 0,
+                snippet: "{99999999999999999999999}".to_string(),
+                kind: ParseErrorKind::NumberOverflow,
+            },
+            CreateMessageError::Parse {
+                offset: 33,
+                snippet: "{0 {1".to_string(),
+                kind: ParseErrorKind::UnclosedPlaceholder,
+            },
+        ]
+    );
+
+    let errors = AllocMessage::<2>::from_str_all("Hello {1}").unwrap_err();
+    assert_eq!(
+        errors,
+        vec![CreateMessageError::WithoutNumber { number: 0, n: 2 }]
+    );
+
+    let message = AllocMessage::<1>::from_str_all("Hello {0}").unwrap();
+    assert_eq!(message.format(&["World!"]), "Hello World!");
+}
+
+#[test]
+#[should_panic(
+    expected = "Invalid message syntax at byte 7: unexpected character 'n' in the placeholder."
+)]
+fn parse_error_panic() {
+    AllocMessage::<1>::from_str("Hello {name}")
+        .unwrap_err()
+        .panic();
+}
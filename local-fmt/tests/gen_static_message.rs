@@ -40,3 +40,44 @@ fn with_i_number() {
     let text = MESSAGE.format(&["World!"]);
     assert_eq!(text, "Hello! World! -123456789");
 }
+
+#[test]
+fn with_float() {
+    const VERSION: f32 = 1.1;
+    const RATIO: f64 = 2.0 / 3.0;
+    const MESSAGE: StaticMessage<1> =
+        gen_static_message!("{0} {f:VERSION} uses {f.2:RATIO} of the memory");
+    let text = MESSAGE.format(&["Ferris"]);
+    assert_eq!(text, "Ferris 1.1 uses 0.67 of the memory");
+}
+
+#[test]
+fn with_char_and_bool() {
+    const SEPARATOR: char = '→';
+    const ENABLED: bool = true;
+    const MESSAGE: StaticMessage<2> = gen_static_message!("{0} {c:SEPARATOR} {1}: {b:ENABLED}");
+    let text = MESSAGE.format(&["Settings", "Sync"]);
+    assert_eq!(text, "Settings → Sync: true");
+}
+
+#[test]
+fn const_format_with_constants() {
+    const TINY: f64 = 5e-324;
+    const SEPARATOR: char = 'é';
+    const ENABLED: bool = false;
+    const MESSAGE: StaticMessage<0> =
+        gen_static_message!("{f:TINY} {f.3:TINY} {c:SEPARATOR} {b:ENABLED}");
+    const TEXT: &str = {
+        const BUFFER: local_fmt::UtilBufWrapper<1024> = unsafe { MESSAGE.const_format(&[]) };
+        BUFFER.as_str()
+    };
+    assert_eq!(TEXT, MESSAGE.format(&[]));
+}
+
+#[test]
+fn with_escapes() {
+    const MESSAGE: StaticMessage<1> = gen_static_message!("{{{0}}} \\{0} \\\\");
+    let text = MESSAGE.format(&["name"]);
+    assert_eq!(text, "{name} {0} \\");
+    assert_eq!(MESSAGE.to_string(), "{{{0}}} {{0}} \\\\");
+}
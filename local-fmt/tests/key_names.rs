@@ -0,0 +1,57 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct AccountMessages {
+    pub display_name: &'static str,
+    pub close: &'static str,
+}
+
+struct Messages {
+    pub sign_in: &'static str,
+    pub two_factor_prompt: StaticMessage<1>,
+    pub account_settings: AccountMessages,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages {
+        account_settings: AccountMessages,
+    },
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/key_names",
+    rename_all = "kebab-case",
+    rename = {
+        two_factor_prompt = "2fa-prompt",
+        account_settings.close = "delete-account",
+    },
+);
+
+#[test]
+fn key_names() {
+    assert_eq!(MESSAGES.sign_in, "Sign in");
+    assert_eq!(
+        MESSAGES.two_factor_prompt.format(&["you@example.com"]),
+        "Enter the code sent to you@example.com"
+    );
+    assert_eq!(MESSAGES.account_settings.display_name, "Display name");
+    assert_eq!(MESSAGES.account_settings.close, "Delete account");
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(MESSAGES.sign_in, "サインイン");
+    assert_eq!(MESSAGES.account_settings.close, "アカウントを削除");
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Words {
+    pub product: &'static str,
+    pub ownership: &'static str,
+    pub version: u32,
+}
+
+struct Messages {
+    pub welcome: StaticMessage<1>,
+    pub about: &'static str,
+    pub signed_in: StaticMessage<2>,
+    pub greeting: StaticMessage<1>,
+    pub unread: StaticMessage<1>,
+    pub inbox: StaticMessage<2>,
+    pub words: Words,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages { words: Words },
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/key_refs",
+    accessors = EN,
+);
+
+#[test]
+fn key_refs() {
+    assert_eq!(
+        MESSAGES.welcome.format(&["Ferris"]),
+        "Welcome to Rustacean Hub, Ferris!"
+    );
+    assert_eq!(MESSAGES.about, "Rustacean Hub 2 is about ownership.");
+    assert_eq!(
+        MESSAGES.signed_in.format(&["Ferris", "3"]),
+        "Hello, Ferris. You have 3 new messages."
+    );
+    assert_eq!(MESSAGES.greeting.format(&["Ferris"]), "Hello, Ferris.");
+    assert_eq!(MESSAGES.words.product, "Rustacean Hub");
+    assert_eq!(MESSAGES.words.ownership, "ownership");
+    assert_eq!(MESSAGES.words.version, 2);
+    // The name and kind of `{0=count:number}` in `unread` are those of `{0}` in `inbox`.
+    assert_eq!(MESSAGES.unread.format(&["3"]), "3 unread messages");
+    assert_eq!(MESSAGES.inbox(3, "Inbox"), "3 unread messages in Inbox");
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(
+        MESSAGES.welcome.format(&["Ferris"]),
+        "Ferrisさん、ラスタシアン・ハブへようこそ！"
+    );
+    assert_eq!(
+        MESSAGES.about,
+        "ラスタシアン・ハブ 2 は所有権についてです。"
+    );
+    assert_eq!(
+        MESSAGES.signed_in.format(&["Ferris", "3"]),
+        "こんにちは、Ferrisさん。新着メッセージが3件あります。"
+    );
+    assert_eq!(MESSAGES.inbox(3, "受信箱"), "受信箱に未読メッセージ3件");
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::{
+    fmt::{self, Display},
+    sync::RwLock,
+};
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, DateArg, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct AccountMessages {
+    pub expires: StaticMessage<1>,
+}
+
+struct Messages {
+    pub inbox: StaticMessage<3>,
+    pub account: AccountMessages,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages {
+        account: AccountMessages,
+    },
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/kinds",
+    accessors = EN,
+);
+
+struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl DateArg for Date {}
+
+#[test]
+fn kinds() {
+    let date = Date {
+        year: 2024,
+        month: 4,
+        day: 1,
+    };
+
+    assert_eq!(
+        MESSAGES.inbox("Ferris", 3u32, &date),
+        "Ferris, you have 3 new messages since 2024-04-01."
+    );
+    assert_eq!(
+        MESSAGES.account.expires(&date),
+        "Your account expires on 2024-04-01."
+    );
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(
+        MESSAGES.inbox("Ferris", 3u32, &date),
+        "Ferrisさん、2024-04-01以降に3件の新着メッセージがあります。"
+    );
+    assert_eq!(
+        MESSAGES.account.expires(date),
+        "アカウントは2024-04-01に期限切れになります。"
+    );
+}
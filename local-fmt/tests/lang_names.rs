@@ -0,0 +1,44 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+#![cfg(feature = "macros-json")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EnUs,
+    DeDe,
+    JA,
+}
+
+struct Messages {
+    pub hello: StaticMessage<1>,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EnUs);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages,
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/lang_names",
+    lang_names = { JA = "ja" },
+    lang_rename = "PascalCase",
+);
+
+#[test]
+fn lang_names() {
+    assert_eq!(MESSAGES.hello.format(&["Rust"]), "Hello, Rust!");
+
+    *LANG.write().unwrap() = Lang::DeDe;
+
+    assert_eq!(MESSAGES.hello.format(&["Rust"]), "Hallo, Rust!");
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(MESSAGES.hello.format(&["Rust"]), "こんにちは、Rust！");
+}
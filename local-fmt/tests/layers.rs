@@ -0,0 +1,73 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+#![cfg(feature = "macros-json")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, MessageLayer, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Words {
+    pub brand: &'static str,
+    pub support: &'static str,
+}
+
+struct Messages {
+    pub hello: StaticMessage<1>,
+    pub words: Words,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages { words: Words },
+    supplier = || *LANG.read().unwrap(),
+    lang_layers = [
+        (file_type = "toml", lang_folder = "tests/layers/base"),
+        (file_type = "json", lang_file = "tests/layers/customer.json"),
+    ],
+    layer_report = MESSAGES_LAYERS,
+);
+
+#[test]
+fn layers() {
+    assert_eq!(MESSAGES.hello.format(&["Rust"]), "Hello, Rust!");
+    assert_eq!(MESSAGES.words.brand, "Acme");
+    assert_eq!(MESSAGES.words.support, "Contact support");
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(MESSAGES.hello.format(&["Rust"]), "ようこそ、Rustさん！");
+    assert_eq!(MESSAGES.words.brand, "アクメ");
+    assert_eq!(MESSAGES.words.support, "サポートに連絡");
+}
+
+#[test]
+fn layer_report() {
+    let layer = |lang: &'static str, key: &'static str, layer: usize| MessageLayer {
+        lang,
+        key,
+        layer,
+        source: ["tests/layers/base", "tests/layers/customer.json"][layer],
+    };
+
+    assert_eq!(
+        MESSAGES_LAYERS,
+        [
+            layer("EN", "hello", 0),
+            layer("EN", "words.brand", 1),
+            layer("EN", "words.support", 0),
+            layer("JA", "hello", 1),
+            layer("JA", "words.brand", 1),
+            layer("JA", "words.support", 0),
+        ]
+    );
+}
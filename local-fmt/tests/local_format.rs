@@ -0,0 +1,41 @@
+use std::fmt::{Display, Write};
+
+use local_fmt::{gen_static_message, local_format, local_format_args, local_write, StaticMessage};
+
+const HELLO: StaticMessage<1> = gen_static_message!("Hello, {0}!");
+const INBOX: StaticMessage<2> = gen_static_message!("{0} has {1} new messages, {0}");
+const TITLE: StaticMessage<0> = gen_static_message!("Inbox");
+
+#[test]
+fn local_format() {
+    assert_eq!(local_format!(HELLO, "Rust"), "Hello, Rust!");
+    assert_eq!(
+        local_format!(INBOX, String::from("Ferris"), 3),
+        "Ferris has 3 new messages, Ferris"
+    );
+    assert_eq!(local_format!(TITLE), "Inbox");
+}
+
+#[test]
+fn local_write() {
+    struct Inbox(u32);
+
+    impl Display for Inbox {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            local_write!(f, INBOX, "Ferris", self.0)
+        }
+    }
+
+    assert_eq!(Inbox(2).to_string(), "Ferris has 2 new messages, Ferris");
+
+    let mut text = String::new();
+    local_write!(text, HELLO, 'R').unwrap();
+    assert_eq!(text, "Hello, R!");
+}
+
+#[test]
+fn local_format_args() {
+    let result = std::panic::catch_unwind(|| panic!("{}", local_format_args!(HELLO, "panic")));
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<String>().unwrap(), "Hello, panic!");
+}
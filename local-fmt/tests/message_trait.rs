@@ -0,0 +1,78 @@
+use std::str::FromStr;
+
+use local_fmt::{AllocMessage, ConstFloat, Message, RefMessage, RefMessageFormat, StaticMessage};
+
+fn describe<const N: usize>(message: &impl Message<N>, args: &[&str; N]) -> (usize, usize, String) {
+    (
+        message.args(),
+        message.segments().count(),
+        message.format(args),
+    )
+}
+
+const STATIC: StaticMessage<1> = StaticMessage::new_panic(&[
+    RefMessageFormat::RefText("Hello, "),
+    RefMessageFormat::Placeholder(0),
+    RefMessageFormat::RefText("! "),
+    RefMessageFormat::UNumber(42),
+    RefMessageFormat::Char('-'),
+    RefMessageFormat::Bool(true),
+]);
+
+#[test]
+fn generic_over_messages() {
+    assert_eq!(
+        describe(&STATIC, &["Rust"]),
+        (1, 6, "Hello, Rust! 42-true".to_string())
+    );
+
+    let alloc = AllocMessage::<1>::from_str("Hello, {0}!").unwrap();
+    assert_eq!(
+        describe(&alloc, &["Rust"]),
+        (1, 3, "Hello, Rust!".to_string())
+    );
+
+    let text: &'static str = "Hello!";
+    assert_eq!(describe(&text, &[]), (0, 1, "Hello!".to_string()));
+
+    let empty: &'static str = "";
+    assert_eq!(describe(&empty, &[]), (0, 0, String::new()));
+}
+
+#[test]
+fn segments() {
+    let alloc = AllocMessage::<2>::from_str("{1} and {0}").unwrap();
+    assert_eq!(
+        alloc.segments().collect::<Vec<_>>(),
+        vec![
+            RefMessageFormat::Placeholder(1),
+            RefMessageFormat::RefText(" and "),
+            RefMessageFormat::Placeholder(0),
+        ]
+    );
+}
+
+#[test]
+fn as_ref_message() {
+    let alloc = AllocMessage::<2>::from_str("{1} and {0}").unwrap();
+    let buf = alloc.as_ref_message();
+    let message: RefMessage<2> = buf.message();
+
+    assert_eq!(message.format(&["a", "b"]), "b and a");
+    assert_eq!(describe(&buf, &["a", "b"]), (2, 3, "b and a".to_string()));
+    assert_eq!(message.to_alloc(), alloc);
+}
+
+#[test]
+fn to_alloc() {
+    let alloc = STATIC.to_alloc();
+    assert_eq!(alloc.to_string(), "Hello, {0}! 42-true");
+    assert_eq!(alloc.format(&["Rust"]), STATIC.format(&["Rust"]));
+
+    const FLOAT: StaticMessage<0> = StaticMessage::new_panic(&[
+        RefMessageFormat::RefText("value is "),
+        RefMessageFormat::Float(ConstFloat::new(1.5f64, None)),
+    ]);
+    assert_eq!(FLOAT.to_alloc().formats().len(), 1);
+    assert_eq!(FLOAT.to_alloc().format(&[]), "value is 1.5");
+}
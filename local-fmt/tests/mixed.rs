@@ -0,0 +1,42 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+#![cfg(feature = "macros-json")]
+// README.md of the folder is ignored on purpose, which def_local_fmt reports as a deprecation warning.
+#![allow(deprecated)]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Messages {
+    pub hello: StaticMessage<1>,
+    pub docs: &'static str,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages,
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/mixed"
+);
+
+#[test]
+fn mixed() {
+    assert_eq!(MESSAGES.hello.format(&["Rust"]), "Hello, Rust!");
+    assert_eq!(MESSAGES.docs, "See https://docs.rs/local-fmt");
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(MESSAGES.hello.format(&["Rust"]), "こんにちは、Rust！");
+    assert_eq!(MESSAGES.docs, "https://docs.rs/local-fmt を参照");
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Common {
+    pub hello: StaticMessage<1>,
+}
+
+struct Http {
+    pub timeout: StaticMessage<1>,
+}
+
+struct Errors {
+    pub not_found: &'static str,
+    pub http: Http,
+}
+
+struct Messages {
+    pub common: Common,
+    pub errors: Errors,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages {
+        common: Common,
+        errors: Errors { http: Http },
+    },
+    supplier = || *LANG.read().unwrap(),
+    file_type = "toml",
+    lang_folder = "tests/namespace"
+);
+
+#[test]
+fn namespace() {
+    assert_eq!(MESSAGES.common.hello.format(&["Rust"]), "Hello, Rust!");
+    assert_eq!(MESSAGES.errors.not_found, "Not found");
+    assert_eq!(
+        MESSAGES.errors.http.timeout.format(&["30"]),
+        "Timed out after 30 seconds"
+    );
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(
+        MESSAGES.common.hello.format(&["Rust"]),
+        "こんにちは、Rust！"
+    );
+    assert_eq!(MESSAGES.errors.not_found, "見つかりません");
+    assert_eq!(
+        MESSAGES.errors.http.timeout.format(&["30"]),
+        "30秒後にタイムアウトしました"
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::{str::FromStr, sync::RwLock};
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, local_format, local_format_args, AllocMessage, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Units {
+    pub kilometers: StaticMessage<1>,
+    pub unknown: &'static str,
+}
+
+struct Messages {
+    pub distance: StaticMessage<1>,
+    pub route: StaticMessage<3>,
+    pub units: Units,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages { units: Units },
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/nested_messages",
+    accessors = EN,
+);
+
+#[test]
+fn nested_messages() {
+    assert_eq!(
+        MESSAGES.distance(local_format_args!(MESSAGES.units.kilometers, 42)),
+        "The distance is 42 km."
+    );
+    assert_eq!(
+        MESSAGES.distance(local_format_args!(MESSAGES.units.unknown)),
+        "The distance is unknown."
+    );
+    assert_eq!(
+        MESSAGES.route(
+            local_format_args!(
+                MESSAGES.distance,
+                local_format_args!(MESSAGES.units.kilometers, 3)
+            ),
+            "Tokyo",
+            "Yokohama",
+        ),
+        "The distance is 3 km. from Tokyo to Yokohama"
+    );
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(
+        MESSAGES.distance(local_format_args!(MESSAGES.units.kilometers, 42)),
+        "距離は42キロです。"
+    );
+    assert_eq!(
+        MESSAGES.distance(local_format_args!(MESSAGES.units.unknown)),
+        "距離は不明です。"
+    );
+}
+
+#[test]
+fn alloc_message_as_argument() {
+    let unit = AllocMessage::<1>::from_str("{0} miles").unwrap();
+    let outer = AllocMessage::<1>::from_str("Only {0} left").unwrap();
+
+    assert_eq!(
+        local_format!(outer, local_format_args!(unit, 5)),
+        "Only 5 miles left"
+    );
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+#![cfg(feature = "macros-json")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::def_local_fmt;
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct CalendarMessages {
+    pub first_weekday: u8,
+    pub utc_offset: i32,
+    pub week_numbers: bool,
+}
+
+struct Messages {
+    pub hello: &'static str,
+    pub max_items: u32,
+    pub version: &'static str,
+    pub calendar: CalendarMessages,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages {
+        calendar: CalendarMessages,
+    },
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/scalars",
+);
+
+struct Ratios {
+    pub ratio: f64,
+    pub scale: f32,
+}
+
+def_local_fmt!(
+    name = RATIOS,
+    lang = Lang,
+    message = Ratios,
+    supplier = || *LANG.read().unwrap(),
+    lang_file = "tests/scalars.json",
+);
+
+#[test]
+fn scalars() {
+    assert_eq!(MESSAGES.hello, "Hello");
+    assert_eq!(MESSAGES.max_items, 10);
+    assert_eq!(MESSAGES.version, "2.0");
+    assert_eq!(MESSAGES.calendar.first_weekday, 0);
+    assert_eq!(MESSAGES.calendar.utc_offset, -5);
+    assert!(MESSAGES.calendar.week_numbers);
+    assert_eq!(RATIOS.ratio, 0.5);
+    assert_eq!(RATIOS.scale, 2.0);
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(MESSAGES.hello, "こんにちは");
+    assert_eq!(MESSAGES.max_items, 20);
+    assert_eq!(MESSAGES.version, "1.1");
+    assert_eq!(MESSAGES.calendar.first_weekday, 1);
+    assert_eq!(MESSAGES.calendar.utc_offset, 9);
+    assert!(!MESSAGES.calendar.week_numbers);
+    assert_eq!(RATIOS.ratio, 1.0);
+    assert_eq!(RATIOS.scale, 1.5);
+}
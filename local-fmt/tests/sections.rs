@@ -0,0 +1,192 @@
+use std::{
+    cell::Cell,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use local_fmt::{
+    gen_alloc_message, gen_static_message, local_format, AllocMessage, AllocMessageFormat,
+    CreateMessageError, DynMessage, ParseErrorKind, RefMessage, RefMessageFormat, StaticMessage,
+    UtilBufWrapper,
+};
+
+const SIGNED_IN: StaticMessage<2> = gen_static_message!("Signed in as {0|guest}{1? ({1})}");
+
+#[test]
+fn static_message() {
+    assert_eq!(
+        SIGNED_IN.formats(),
+        &[
+            RefMessageFormat::RefText("Signed in as "),
+            RefMessageFormat::PlaceholderDefault(0, "guest"),
+            RefMessageFormat::SectionStart(1),
+            RefMessageFormat::RefText(" ("),
+            RefMessageFormat::Placeholder(1),
+            RefMessageFormat::RefText(")"),
+            RefMessageFormat::SectionEnd,
+        ]
+    );
+    assert_eq!(
+        SIGNED_IN.format(&["Alice", "admin"]),
+        "Signed in as Alice (admin)"
+    );
+    assert_eq!(SIGNED_IN.format(&["Alice", ""]), "Signed in as Alice");
+    assert_eq!(SIGNED_IN.format(&["", ""]), "Signed in as guest");
+    assert_eq!(SIGNED_IN.to_string(), "Signed in as {0|guest}{1? ({1})}");
+}
+
+#[test]
+fn const_format() {
+    const TEXT: &str = {
+        const BUF: UtilBufWrapper<32> = unsafe { SIGNED_IN.const_format::<32>(&[b"", b""]) };
+        BUF.as_str()
+    };
+    assert_eq!(TEXT, "Signed in as guest");
+
+    const NESTED: StaticMessage<2> = gen_static_message!("[{0? a{1? b} c}]");
+    const EMPTY: &str = {
+        const BUF: UtilBufWrapper<16> = unsafe { NESTED.const_format::<16>(&[b"", b"x"]) };
+        BUF.as_str()
+    };
+    assert_eq!(EMPTY, "[]");
+    const FULL: &str = {
+        const BUF: UtilBufWrapper<16> = unsafe { NESTED.const_format::<16>(&[b"x", b"x"]) };
+        BUF.as_str()
+    };
+    assert_eq!(FULL, "[ a b c]");
+}
+
+#[test]
+fn alloc_message() {
+    let message = AllocMessage::<2>::from_str("Signed in as {0|guest}{1? ({1})}").unwrap();
+    assert_eq!(
+        message.format(&["Alice", "admin"]),
+        "Signed in as Alice (admin)"
+    );
+    assert_eq!(message.format(&["", ""]), "Signed in as guest");
+    assert_eq!(message, SIGNED_IN.to_alloc());
+
+    let message: AllocMessage<2> = gen_alloc_message!("{0|guest}{1? ({1})}");
+    assert_eq!(message.format(&["", "admin"]), "guest (admin)");
+}
+
+#[test]
+fn nested_sections() {
+    let message = AllocMessage::<2>::from_str("[{0? a{1? b} c}]").unwrap();
+    assert_eq!(message.format(&["x", "x"]), "[ a b c]");
+    assert_eq!(message.format(&["x", ""]), "[ a c]");
+    assert_eq!(message.format(&["", "x"]), "[]");
+}
+
+#[test]
+fn escapes() {
+    let message = AllocMessage::<1>::from_str("{0|\\{none\\}}{0? \\}{{\\}}").unwrap();
+    assert_eq!(message.format(&[""]), "{none}");
+    assert_eq!(message.format(&["x"]), "x }{}");
+
+    let text = message.to_string();
+    assert_eq!(text, "{0|\\{none\\}}{0? \\}{{\\}}");
+    assert_eq!(AllocMessage::<1>::from_str(&text).unwrap(), message);
+
+    let message = AllocMessage::<1>::from_str("{0? a}}}").unwrap();
+    assert_eq!(message.format(&["x"]), " a}");
+    assert_eq!(
+        AllocMessage::<1>::from_str(&message.to_string()).unwrap(),
+        message
+    );
+}
+
+#[test]
+fn errors() {
+    let errors = AllocMessage::<2>::from_str_all("{0? a {1|b} {1? c").unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            CreateMessageError::Parse {
+                offset: 0,
+                snippet: "{0?".to_string(),
+                kind: ParseErrorKind::UnclosedSection,
+            },
+            CreateMessageError::Parse {
+                offset: 12,
+                snippet: "{1?".to_string(),
+                kind: ParseErrorKind::UnclosedSection,
+            },
+        ]
+    );
+
+    let error = AllocMessage::<1>::from_str("{a|b}").unwrap_err();
+    assert_eq!(
+        error,
+        CreateMessageError::Parse {
+            offset: 1,
+            snippet: "{a|b}".to_string(),
+            kind: ParseErrorKind::UnexpectedChar('a'),
+        }
+    );
+
+    let error = AllocMessage::<1>::new(vec![
+        AllocMessageFormat::SectionStart(0),
+        AllocMessageFormat::Placeholder(0),
+    ])
+    .unwrap_err();
+    assert_eq!(error, CreateMessageError::UnbalancedSection);
+
+    let error = RefMessage::<0>::new(&[RefMessageFormat::SectionEnd]).unwrap_err();
+    assert_eq!(error, CreateMessageError::UnbalancedSection);
+}
+
+#[test]
+fn arguments_of_sections() {
+    let message = DynMessage::from_str("{0|none}{1? and more}").unwrap();
+    assert_eq!(message.args(), 2);
+    assert_eq!(message.format(&["", "x"]).unwrap(), "none and more");
+
+    let error = AllocMessage::<3>::from_str("{0|none}{1? and more}").unwrap_err();
+    assert_eq!(error, CreateMessageError::WithoutNumber { number: 2, n: 3 });
+}
+
+#[test]
+fn local_format() {
+    assert_eq!(
+        local_format!(SIGNED_IN, "Alice", 42),
+        "Signed in as Alice (42)"
+    );
+    assert_eq!(local_format!(SIGNED_IN, "", ""), "Signed in as guest");
+    assert_eq!(
+        local_format!(SIGNED_IN, 'a', String::new()),
+        "Signed in as a"
+    );
+}
+
+#[test]
+fn arguments_are_formatted_once() {
+    /// Counts how many times it is formatted, and unwraps its writes.
+    struct Counted<'a>(&'a Cell<usize>, &'static str);
+
+    impl Display for Counted<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.set(self.0.get() + 1);
+            f.write_str(self.1).unwrap();
+            Ok(())
+        }
+    }
+
+    let count = Cell::new(0);
+    assert_eq!(
+        local_format!(
+            SIGNED_IN,
+            Counted(&count, "Alice"),
+            Counted(&count, "admin")
+        ),
+        "Signed in as Alice (admin)"
+    );
+    assert_eq!(count.get(), 2);
+
+    let count = Cell::new(0);
+    assert_eq!(
+        local_format!(SIGNED_IN, Counted(&count, ""), Counted(&count, "")),
+        "Signed in as guest"
+    );
+    assert_eq!(count.get(), 2);
+}
@@ -50,3 +50,55 @@ fn de_alloc() {
 
     assert_eq!(test.message, expected_message);
 }
+
+#[test]
+fn de_alloc_invalid_number() {
+    let text = "message = 'Hello, {0} and {1}'";
+    let error = toml::from_str::<Alloc>(text).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("Invalid argument number: 1 is out of the allowed range (0 <= number < 1)."));
+}
+
+#[test]
+fn de_alloc_without_number() {
+    let text = "message = 'Hello, alloc!'";
+    let error = toml::from_str::<Alloc>(text).unwrap_err();
+    assert!(error.to_string().contains(
+        "Missing argument number: 0 is not found within the allowed range (0 <= number < 1)."
+    ));
+}
+
+#[test]
+fn round_trip_escaped() {
+    let alloc_struct = Alloc {
+        message: AllocMessage::new_panic(vec![
+            AllocMessageFormat::AllocText("{literal} \\".to_string()),
+            AllocMessageFormat::Placeholder(0),
+            AllocMessageFormat::AllocText("}".to_string()),
+        ]),
+    };
+
+    let text = toml::to_string(&alloc_struct).unwrap();
+    assert_eq!(text, "message = '{{literal}} \\\\{0}}}'\n");
+    let test: Alloc = toml::from_str(&text).unwrap();
+    assert_eq!(test, alloc_struct);
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Catalog {
+    messages: std::collections::BTreeMap<String, local_fmt::DynMessage>,
+}
+
+#[test]
+fn de_dyn() {
+    let text = "[messages]\nhello = \"Hello, {0}!\"\nsigned_in = \"{0} has {1} new messages\"\n";
+    let catalog: Catalog = toml::from_str(text).unwrap();
+
+    assert_eq!(catalog.messages["hello"].args(), 1);
+    assert_eq!(catalog.messages["signed_in"].args(), 2);
+    assert_eq!(toml::to_string(&catalog).unwrap(), text);
+
+    let error = toml::from_str::<Catalog>("[messages]\nhello = 'Hello, {name}!'\n").unwrap_err();
+    assert!(error.to_string().contains("unexpected character 'n'"));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Messages {
+    pub welcome: StaticMessage<1>,
+    pub farewell: &'static str,
+    pub contact: &'static str,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages,
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/terms",
+);
+
+#[test]
+fn terms() {
+    assert_eq!(
+        MESSAGES.welcome.format(&["Ferris"]),
+        "Welcome to Rustacean Hub, Ferris!"
+    );
+    assert_eq!(MESSAGES.farewell, "Thank you for using Rustacean Hub.");
+    assert_eq!(MESSAGES.contact, "Contact the Ferris team for help.");
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(
+        MESSAGES.welcome.format(&["Ferris"]),
+        "Ferris様、ラスタシアン・ハブへようこそ！"
+    );
+    assert_eq!(
+        MESSAGES.farewell,
+        "ラスタシアン・ハブをご利用いただきありがとうございます。"
+    );
+    assert_eq!(
+        MESSAGES.contact,
+        "お困りの際はFerrisチームまでご連絡ください。"
+    );
+}
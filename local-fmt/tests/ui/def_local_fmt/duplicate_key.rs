@@ -0,0 +1,27 @@
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Messages {
+    pub items: StaticMessage<1>,
+    pub errors: Errors,
+}
+
+struct Errors {
+    pub not_found: &'static str,
+}
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages { errors: Errors },
+    supplier = || Lang::EN,
+    lang_folder = "../../../../local-fmt/tests/ui/def_local_fmt/duplicate_key",
+);
+
+fn main() {}
@@ -0,0 +1,24 @@
+#![deny(deprecated)]
+
+use enum_table::Enumable;
+use local_fmt::def_local_fmt;
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Messages {
+    pub hello: &'static str,
+}
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages,
+    supplier = || Lang::EN,
+    lang_folder = "../../../../local-fmt/tests/ui/def_local_fmt/ignored_files",
+);
+
+fn main() {}
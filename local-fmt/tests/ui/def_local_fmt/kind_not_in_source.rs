@@ -0,0 +1,23 @@
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Messages {
+    pub items: StaticMessage<1>,
+}
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages,
+    supplier = || Lang::EN,
+    lang_file = "../../../../local-fmt/tests/ui/def_local_fmt/kind_not_in_source.toml",
+    accessors = EN,
+);
+
+fn main() {}
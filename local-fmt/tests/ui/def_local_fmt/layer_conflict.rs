@@ -0,0 +1,25 @@
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct Messages {
+    pub items: StaticMessage<1>,
+}
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages,
+    supplier = || Lang::EN,
+    lang_layers = [
+        (lang_file = "../../../../local-fmt/tests/ui/def_local_fmt/layer_conflict/base.toml"),
+        (lang_file = "../../../../local-fmt/tests/ui/def_local_fmt/layer_conflict/custom.toml"),
+    ],
+);
+
+fn main() {}
@@ -0,0 +1,5 @@
+use local_fmt::{gen_static_message, StaticMessage};
+
+const _: StaticMessage<0> = gen_static_message!("Welcome to {@words.product}");
+
+fn main() {}
@@ -0,0 +1,7 @@
+use local_fmt::{gen_static_message, StaticMessage};
+
+const _: StaticMessage<1> = gen_static_message!("{0}{99999999999999999999999? more}");
+
+const _: StaticMessage<1> = gen_static_message!("{99999999999999999999999}");
+
+fn main() {}
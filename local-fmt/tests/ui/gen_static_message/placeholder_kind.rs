@@ -0,0 +1,7 @@
+use local_fmt::{gen_alloc_message, gen_static_message, AllocMessage, StaticMessage};
+
+const _: StaticMessage<1> = gen_static_message!("Hello, {0=user:text}!");
+
+fn main() {
+    let _: AllocMessage<1> = gen_alloc_message!("{0:number} km");
+}
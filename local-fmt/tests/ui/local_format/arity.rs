@@ -0,0 +1,7 @@
+use local_fmt::{gen_static_message, local_format, StaticMessage};
+
+const MESSAGE: StaticMessage<2> = gen_static_message!("{0} and {1}");
+
+fn main() {
+    let _ = local_format!(MESSAGE, "one");
+}
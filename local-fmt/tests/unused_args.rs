@@ -0,0 +1,127 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, AllocMessage, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+struct Account {
+    pub deleted: StaticMessage<1>,
+}
+
+struct Messages {
+    pub inbox: StaticMessage<2>,
+    pub greeting: StaticMessage<1>,
+    pub account: Account,
+}
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages { account: Account },
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/unused_args",
+    allow_unused_args = [inbox, greeting, account],
+);
+
+struct AllocAccount {
+    pub deleted: AllocMessage<1>,
+}
+
+struct AllocMessages {
+    pub inbox: AllocMessage<2>,
+    pub greeting: AllocMessage<1>,
+    pub account: AllocAccount,
+}
+
+def_local_fmt!(
+    name = static ALLOC_MESSAGES,
+    lang = Lang,
+    message = AllocMessages { account: AllocAccount },
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/unused_args",
+    allow_unused_args = true,
+);
+
+#[test]
+fn unused_args() {
+    assert_eq!(
+        MESSAGES.inbox.format(&["Ferris", "3"]),
+        "Ferris, you have 3 new messages."
+    );
+    assert_eq!(MESSAGES.greeting.format(&["Ferris"]), "Hello, Ferris!");
+    assert_eq!(
+        MESSAGES.account.deleted.format(&["Ferris"]),
+        "The account of Ferris was deleted."
+    );
+    assert_eq!(
+        ALLOC_MESSAGES.inbox.format(&["Ferris", "3"]),
+        "Ferris, you have 3 new messages."
+    );
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(
+        MESSAGES.inbox.format(&["Ferris", "3"]),
+        "新着メッセージが3件あります。"
+    );
+    assert_eq!(MESSAGES.greeting.format(&["Ferris"]), "こんにちは！");
+    // The unused arguments are not part of the text.
+    assert_eq!(
+        MESSAGES.inbox.to_string(),
+        "新着メッセージが{1}件あります。"
+    );
+    assert_eq!(
+        ALLOC_MESSAGES.inbox.to_string(),
+        "新着メッセージが{1}件あります。"
+    );
+    assert_eq!(
+        MESSAGES.account.deleted.format(&["Ferris"]),
+        "アカウントが削除されました。"
+    );
+    assert_eq!(
+        ALLOC_MESSAGES.inbox.format(&["Ferris", "3"]),
+        "新着メッセージが3件あります。"
+    );
+    assert_eq!(ALLOC_MESSAGES.greeting.format(&["Ferris"]), "こんにちは！");
+    #[cfg(feature = "serde")]
+    serialized_text(&ALLOC_MESSAGES.greeting);
+    assert_eq!(
+        ALLOC_MESSAGES.account.deleted.format(&["Ferris"]),
+        "アカウントが削除されました。"
+    );
+}
+
+/// The messages that omit an argument keep their number of arguments apart from their text,
+/// so that they are serialized as the text of the language.
+#[cfg(feature = "serde")]
+fn serialized_text(message: &AllocMessage<1>) {
+    use local_fmt::DynMessage;
+
+    #[derive(Debug, PartialEq, serde::Serialize)]
+    struct Greeting {
+        message: AllocMessage<1>,
+    }
+
+    let greeting = Greeting {
+        message: message.clone(),
+    };
+    let text = toml::to_string(&greeting).unwrap();
+    assert_eq!(text, "message = \"こんにちは！\"\n");
+    assert_eq!(MESSAGES.greeting.to_string(), "こんにちは！");
+
+    let message = DynMessage::from(greeting.message);
+    assert_eq!(message.args(), 1);
+    assert_eq!(message.to_string(), "こんにちは！");
+    assert_eq!(message.format(&["Ferris"]).unwrap(), "こんにちは！");
+    assert_eq!(DynMessage::from(MESSAGES.greeting.clone()).args(), 1);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "macros")]
+#![cfg(feature = "macros-toml")]
+
+use std::sync::RwLock;
+
+use enum_table::Enumable;
+use local_fmt::{def_local_fmt, StaticMessage};
+
+#[derive(Clone, Copy, Enumable)]
+enum Lang {
+    EN,
+    JA,
+}
+
+struct HelpMessages {
+    pub usage: StaticMessage<1>,
+}
+
+struct Messages {
+    pub title: &'static str,
+    pub description: &'static str,
+    pub help: HelpMessages,
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::EN);
+
+def_local_fmt!(
+    name = MESSAGES,
+    lang = Lang,
+    message = Messages { help: HelpMessages },
+    supplier = || *LANG.read().unwrap(),
+    lang_folder = "tests/whitespace",
+    whitespace = [trim, join_lines],
+    key_whitespace = { help = [dedent, trim] },
+);
+
+#[test]
+fn whitespace() {
+    assert_eq!(MESSAGES.title, "Welcome");
+    assert_eq!(
+        MESSAGES.description,
+        "This message is wrapped over several lines.\nThis is a second paragraph."
+    );
+    assert_eq!(
+        MESSAGES.help.usage.format(&["--release"]),
+        "Usage:\n  app run --release"
+    );
+
+    *LANG.write().unwrap() = Lang::JA;
+
+    assert_eq!(MESSAGES.title, "ようこそ");
+    assert_eq!(
+        MESSAGES.description,
+        "このメッセージは複数行に分かれています。"
+    );
+    assert_eq!(
+        MESSAGES.help.usage.format(&["--release"]),
+        "使い方:\n  app run --release"
+    );
+}